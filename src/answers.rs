@@ -0,0 +1,148 @@
+//! The known correct answers of each day, stored next to the input in `dayN/answers`.
+//!
+//! Every part is written on its own line as `part1: <answer>`. Answers that span multiple lines
+//! leave the value empty and continue on the following lines, each prefixed with `|`. Empty lines
+//! and lines starting with `//` are ignored:
+//!
+//! ```text
+//! // The Stars Align
+//! part1:
+//! |#   #
+//! |#####
+//! part2: 10355
+//! ```
+
+use crate::input::{self, InputError};
+use crate::parse::lines;
+use crate::{Answers, Error, ParseError};
+use std::path::PathBuf;
+
+/// The expected answers of a day. A part is `None` if its answer is not known yet.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExpectedAnswers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// A computed answer that differs from the expected answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub part: u32,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl ExpectedAnswers {
+    pub fn parse(input: &str) -> Result<ExpectedAnswers, ParseError> {
+        let mut answers = ExpectedAnswers::default();
+        let mut current: Option<&mut String> = None;
+        for line in lines(input) {
+            if let Ok(continuation) = line.strip_prefix("|") {
+                match current.as_mut() {
+                    Some(answer) => {
+                        answer.push_str(continuation.text);
+                        answer.push('\n');
+                    }
+                    None => return Err(line.error("continuation line without a part")),
+                }
+                continue;
+            }
+
+            current = None;
+            if line.trim().is_empty() || line.text.starts_with("//") {
+                continue;
+            }
+
+            let (part, value) = match line.text.find(':') {
+                Some(idx) => (line.slice(0..idx), line.slice(idx + 1..line.text.len())),
+                None => return Err(line.error("expected `part1: <answer>`")),
+            };
+            let answer = match part.text {
+                "part1" => &mut answers.part1,
+                "part2" => &mut answers.part2,
+                _ => return Err(part.error("expected `part1` or `part2`")),
+            };
+            if answer.is_some() {
+                return Err(part.error(format!("duplicate answer for {}", part.text)));
+            }
+
+            let value = value.trim();
+            if value.is_empty() {
+                current = Some(answer.get_or_insert_with(String::new));
+            } else {
+                *answer = Some(value.text.to_owned());
+            }
+        }
+        Ok(answers)
+    }
+
+    /// Compares computed answers with the expected answers. Parts without an expected answer are
+    /// not checked.
+    pub fn check(&self, answers: &Answers) -> Vec<Mismatch> {
+        [
+            (1, &self.part1, &answers.part1),
+            (2, &self.part2, &answers.part2),
+        ]
+        .iter()
+        .filter_map(|(part, expected, actual)| match expected {
+            Some(expected) if expected != *actual => Some(Mismatch {
+                part: *part,
+                expected: expected.clone(),
+                actual: (*actual).clone(),
+            }),
+            _ => None,
+        })
+        .collect()
+    }
+}
+
+/// Returns the path of the answers file of a day.
+pub fn path(day: u32) -> PathBuf {
+    input::input_dir()
+        .join(format!("day{}", day))
+        .join("answers")
+}
+
+/// Reads the expected answers of a day, or `None` if there is no answers file for the day.
+pub fn load(day: u32) -> Result<Option<ExpectedAnswers>, Error> {
+    let path = path(day);
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => {
+            return Err(Error::Input(InputError {
+                day,
+                path: Some(path),
+                error,
+            }))
+        }
+    };
+
+    ExpectedAnswers::parse(&contents)
+        .map(Some)
+        .map_err(|error| Error::Parse {
+            day,
+            input: path.display().to_string(),
+            error,
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let answers = ExpectedAnswers::parse("// comment\npart1:\n|# #\n| # \n\npart2: 42\n");
+        assert_eq!(
+            answers,
+            Ok(ExpectedAnswers {
+                part1: Some("# #\n # \n".to_owned()),
+                part2: Some("42".to_owned()),
+            })
+        );
+
+        let error = ExpectedAnswers::parse("part1: 1\npart3: 2").unwrap_err();
+        assert_eq!((error.line, error.column), (2, Some(1)));
+    }
+}
@@ -0,0 +1,73 @@
+//! Helpers for the puzzles in which a battle is simulated over and over with one side boosted
+//! until it wins.
+
+use std::ops::RangeInclusive;
+
+/// Finds the smallest boost in `boosts` for which `simulate` returns an outcome, which it does
+/// only if the boosted side wins, or `None` if there is none. Winning does not necessarily get
+/// easier with a larger boost, since a battle can end in a stalemate, so every boost is tried in
+/// turn, `threads` consecutive boosts at a time in parallel.
+pub fn min_boost_parallel<T, F>(
+    boosts: RangeInclusive<i32>,
+    threads: usize,
+    simulate: F,
+) -> Option<(i32, T)>
+where
+    T: Send,
+    F: Fn(i32) -> Option<T> + Sync,
+{
+    let threads = threads.max(1) as i32;
+    let simulate = &simulate;
+    let (mut first, last) = boosts.into_inner();
+    while first <= last {
+        let batch = first..=last.min(first.saturating_add(threads - 1));
+        let outcomes: Vec<Option<T>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = batch
+                .map(|boost| scope.spawn(move || simulate(boost)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        });
+        let winner = outcomes
+            .into_iter()
+            .enumerate()
+            .find_map(|(i, outcome)| outcome.map(|outcome| (first + i as i32, outcome)));
+        if winner.is_some() {
+            return winner;
+        }
+        first = match first.checked_add(threads) {
+            Some(next) => next,
+            None => break,
+        };
+    }
+    None
+}
+
+/// Returns the number of threads to use for `min_boost_parallel`.
+pub fn available_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn min_boost() {
+        // Only some boosts win
+        let simulate = |boost: i32| {
+            if boost >= 37 && boost != 40 {
+                Some(boost * 2)
+            } else {
+                None
+            }
+        };
+        assert_eq!(min_boost_parallel(4..=100, 1, simulate), Some((37, 74)));
+        assert_eq!(min_boost_parallel(4..=100, 5, simulate), Some((37, 74)));
+        assert_eq!(min_boost_parallel(4..=36, 5, simulate), None);
+        assert_eq!(min_boost_parallel(4..=37, 5, simulate), Some((37, 74)));
+        assert_eq!(min_boost_parallel(40..=100, 3, simulate), Some((41, 82)));
+    }
+}
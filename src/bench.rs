@@ -0,0 +1,118 @@
+use crate::input::{self, InputSource};
+use crate::{Answers, Error, Puzzle, Timings};
+use std::time::Duration;
+
+/// Summary statistics of a number of time measurements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Computes the statistics of the given measurements, which must not be empty. The median of
+    /// an even number of measurements is the mean of the two middle ones.
+    pub fn new(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty(), "no measurements");
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let mid = sorted.len() / 2;
+        let median = if sorted.len() % 2 == 1 {
+            sorted[mid]
+        } else {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        };
+        Stats {
+            min: sorted[0],
+            median,
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+/// The results of solving a puzzle a number of times.
+#[derive(Debug, Clone)]
+pub struct Benchmark {
+    /// The answers of the first run.
+    pub answers: Answers,
+    pub runs: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+    /// The statistics of the total time of each run.
+    pub total: Stats,
+}
+
+impl Benchmark {
+    /// Computes the statistics of the timings of all runs.
+    pub fn new(answers: Answers, timings: &[Timings]) -> Benchmark {
+        let stats = |f: fn(&Timings) -> Duration| {
+            Stats::new(&timings.iter().map(f).collect::<Vec<Duration>>())
+        };
+        Benchmark {
+            answers,
+            runs: timings.len(),
+            parse: stats(|t| t.parse),
+            part1: stats(|t| t.part1),
+            part2: stats(|t| t.part2),
+            total: stats(Timings::total),
+        }
+    }
+}
+
+/// Reads the input of the puzzle once and then solves it `runs` times. Reading the input is not
+/// included in the measurements.
+pub fn run(puzzle: &Puzzle, source: &InputSource, runs: usize) -> Result<Benchmark, Error> {
+    let input = input::load(puzzle.day, source)?;
+    let mut answers = None;
+    let mut timings = Vec::with_capacity(runs);
+    for _ in 0..runs.max(1) {
+        let (run_answers, run_timings) =
+            puzzle.solve_timed(&input).map_err(|error| Error::Parse {
+                day: puzzle.day,
+                input: source.name(puzzle.day),
+                error,
+            })?;
+        answers.get_or_insert(run_answers);
+        timings.push(run_timings);
+    }
+    Ok(Benchmark::new(answers.unwrap(), &timings))
+}
+
+/// Formats a duration with three significant digits in the most readable unit.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    let (value, unit) = if nanos < 1e3 {
+        (nanos, "ns")
+    } else if nanos < 1e6 {
+        (nanos / 1e3, "µs")
+    } else if nanos < 1e9 {
+        (nanos / 1e6, "ms")
+    } else {
+        (nanos / 1e9, "s")
+    };
+    let decimals = if value < 10.0 {
+        2
+    } else if value < 100.0 {
+        1
+    } else {
+        0
+    };
+    format!("{:.*} {}", decimals, value, unit)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let ms = Duration::from_millis;
+        let stats = Stats::new(&[ms(5), ms(1), ms(3)]);
+        assert_eq!((stats.min, stats.median, stats.max), (ms(1), ms(3), ms(5)));
+        assert_eq!(Stats::new(&[ms(4), ms(1), ms(2), ms(9)]).median, ms(3));
+        assert_eq!(format_duration(Duration::from_micros(1234)), "1.23 ms");
+    }
+}
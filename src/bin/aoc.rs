@@ -0,0 +1,428 @@
+use aoc::answers::{self, Mismatch};
+use aoc::bench::{self, format_duration, Benchmark, Stats};
+use aoc::input::InputSource;
+use aoc::{Answers, Error, Puzzle, PUZZLES};
+
+const USAGE: &str = "Usage:
+    aoc [all]                 Runs all days
+    aoc run <day>             Runs a single day
+    aoc range <first> <last>  Runs all days from <first> up to and including <last>
+
+Options:
+    --input <path>            Reads the input of a single day from <path>, or from stdin if
+                              <path> is `-`. Defaults to `$AOC_INPUT_DIR/dayN/input`.
+    --check                   Compares the answers with the expected answers stored in
+                              `$AOC_INPUT_DIR/dayN/answers` and fails on any mismatch.
+    --time                    Shows how long parsing and solving each part took.
+    --runs <n>                Solves every day <n> times and shows the median times, implies
+                              --time. The input is only read once.
+    --json                    Prints the answers and the min, median and max times of every
+                              day as JSON instead of a table.";
+
+/// The text shown in the table for days that could not be run.
+const ERROR_CELL: &str = "(error)";
+
+struct Options {
+    source: InputSource,
+    check: bool,
+    time: bool,
+    runs: usize,
+    json: bool,
+    puzzles: Vec<&'static Puzzle>,
+}
+
+/// The outcome of comparing the answers of a day with the expected answers.
+enum Check {
+    Passed,
+    Failed(Vec<Mismatch>),
+    /// There is no answers file for the day.
+    Unknown,
+    Error(Error),
+}
+
+struct DayResult {
+    puzzle: &'static Puzzle,
+    result: Result<Benchmark, Error>,
+    check: Option<Check>,
+}
+
+fn parse_day(arg: Option<&String>) -> Result<u32, String> {
+    let arg = arg.ok_or_else(|| "Missing day".to_owned())?;
+    arg.parse().map_err(|_| format!("Invalid day: {}", arg))
+}
+
+/// Removes an option and its value from the arguments and returns the value.
+fn take_option(args: &mut Vec<String>, option: &str) -> Result<Option<String>, String> {
+    match args.iter().position(|a| a == option) {
+        None => Ok(None),
+        Some(idx) => {
+            if idx + 1 >= args.len() {
+                return Err(format!("Missing value after {}", option));
+            }
+            let value = args.remove(idx + 1);
+            args.remove(idx);
+            Ok(Some(value))
+        }
+    }
+}
+
+/// Removes the `--input <path>` option from the arguments and returns the selected source.
+fn take_input_source(args: &mut Vec<String>) -> Result<InputSource, String> {
+    let path = take_option(args, "--input")?;
+    Ok(InputSource::from_arg(path.as_deref()))
+}
+
+/// Removes the `--runs <n>` option from the arguments and returns the number of runs.
+fn take_runs(args: &mut Vec<String>) -> Result<Option<usize>, String> {
+    match take_option(args, "--runs")? {
+        None => Ok(None),
+        Some(runs) => match runs.parse() {
+            Ok(runs) if runs > 0 => Ok(Some(runs)),
+            _ => Err(format!("Invalid number of runs: {}", runs)),
+        },
+    }
+}
+
+/// Removes the flag from the arguments and returns whether it was present.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let len = args.len();
+    args.retain(|a| a != flag);
+    args.len() != len
+}
+
+/// Selects the puzzles to run based on the command line arguments.
+fn select_puzzles(args: &[String]) -> Result<Vec<&'static Puzzle>, String> {
+    let (first, last) = match args.first().map(String::as_str) {
+        None | Some("all") => return Ok(PUZZLES.iter().collect()),
+        Some("run") => {
+            let day = parse_day(args.get(1))?;
+            (day, day)
+        }
+        Some("range") => (parse_day(args.get(1))?, parse_day(args.get(2))?),
+        Some(command) => return Err(format!("Unknown command: {}", command)),
+    };
+
+    let puzzles: Vec<&Puzzle> = PUZZLES
+        .iter()
+        .filter(|p| p.day >= first && p.day <= last)
+        .collect();
+    if puzzles.is_empty() {
+        Err(format!("No puzzles found for days {} to {}", first, last))
+    } else {
+        Ok(puzzles)
+    }
+}
+
+fn parse_options(mut args: Vec<String>) -> Result<Options, String> {
+    let source = take_input_source(&mut args)?;
+    let check = take_flag(&mut args, "--check");
+    let runs = take_runs(&mut args)?;
+    let time = take_flag(&mut args, "--time") || runs.is_some();
+    let json = take_flag(&mut args, "--json");
+    let puzzles = select_puzzles(&args)?;
+    if source != InputSource::Default {
+        if puzzles.len() != 1 {
+            return Err("--input can only be used when running a single day".to_owned());
+        }
+        if check {
+            return Err("--check can only be used with the default input".to_owned());
+        }
+    }
+    Ok(Options {
+        source,
+        check,
+        time,
+        runs: runs.unwrap_or(1),
+        json,
+        puzzles,
+    })
+}
+
+fn check(puzzle: &Puzzle, answers: &Answers) -> Check {
+    match answers::load(puzzle.day) {
+        Ok(Some(expected)) => {
+            let mismatches = expected.check(answers);
+            if mismatches.is_empty() {
+                Check::Passed
+            } else {
+                Check::Failed(mismatches)
+            }
+        }
+        Ok(None) => Check::Unknown,
+        Err(e) => Check::Error(e),
+    }
+}
+
+/// Returns the text to show in the table for an answer. Answers spanning multiple lines (like
+/// the message of day 10) are printed below the table instead.
+fn cell(answer: &str) -> &str {
+    if answer.contains('\n') {
+        "(see below)"
+    } else {
+        answer
+    }
+}
+
+/// Prints rows of cells as a table below a header. The first column is aligned to the right.
+fn print_table(header: &[&str], rows: &[Vec<String>]) {
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain(std::iter::once(header[column].chars().count()))
+                .max()
+                .unwrap()
+        })
+        .collect();
+
+    let format_row = |cells: &[&str]| {
+        cells
+            .iter()
+            .enumerate()
+            .map(|(column, cell)| {
+                if column == 0 {
+                    format!("{:>width$}", cell, width = widths[column])
+                } else if column + 1 == cells.len() {
+                    cell.to_string()
+                } else {
+                    format!("{:width$}", cell, width = widths[column])
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" | ")
+    };
+
+    println!("{}", format_row(header));
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|w| "-".repeat(*w))
+            .collect::<Vec<String>>()
+            .join("-+-")
+    );
+    for row in rows {
+        let cells: Vec<&str> = row.iter().map(String::as_str).collect();
+        println!("{}", format_row(&cells));
+    }
+}
+
+fn print_results(results: &[DayResult], options: &Options) {
+    let mut header = vec!["Day", "Title", "Part 1", "Part 2"];
+    if options.check {
+        header.push("Check");
+    }
+    if options.time {
+        header.extend(&["Parse", "Part 1 time", "Part 2 time", "Total"]);
+        if options.runs > 1 {
+            header.extend(&["Min total", "Max total"]);
+        }
+    }
+
+    let rows: Vec<Vec<String>> = results
+        .iter()
+        .map(|result| {
+            let (part1, part2) = match &result.result {
+                Ok(benchmark) => (
+                    cell(&benchmark.answers.part1),
+                    cell(&benchmark.answers.part2),
+                ),
+                Err(_) => (ERROR_CELL, ERROR_CELL),
+            };
+            let mut row = vec![
+                result.puzzle.day.to_string(),
+                result.puzzle.title.to_owned(),
+                part1.to_owned(),
+                part2.to_owned(),
+            ];
+            if options.check {
+                let status = match &result.check {
+                    Some(Check::Passed) => "ok",
+                    Some(Check::Failed(_)) => "FAILED",
+                    Some(Check::Unknown) => "-",
+                    Some(Check::Error(_)) | None => ERROR_CELL,
+                };
+                row.push(status.to_owned());
+            }
+            if options.time {
+                let mut times: Vec<&Stats> = Vec::new();
+                if let Ok(benchmark) = &result.result {
+                    times.extend(&[
+                        &benchmark.parse,
+                        &benchmark.part1,
+                        &benchmark.part2,
+                        &benchmark.total,
+                    ]);
+                }
+                let mut cells: Vec<String> =
+                    times.iter().map(|s| format_duration(s.median)).collect();
+                if options.runs > 1 {
+                    if let Ok(benchmark) = &result.result {
+                        cells.push(format_duration(benchmark.total.min));
+                        cells.push(format_duration(benchmark.total.max));
+                    }
+                }
+                cells.resize(header.len() - row.len(), ERROR_CELL.to_owned());
+                row.extend(cells);
+            }
+            row
+        })
+        .collect();
+    if options.time && options.runs > 1 {
+        println!("Times are the median of {} runs", options.runs);
+        println!();
+    }
+    print_table(&header, &rows);
+
+    for result in results.iter() {
+        let day = result.puzzle.day;
+        match &result.result {
+            Ok(benchmark) => {
+                let answers = &benchmark.answers;
+                for (part, answer) in [(1, &answers.part1), (2, &answers.part2)].iter() {
+                    if answer.contains('\n') {
+                        println!();
+                        println!("Day {} part {}:", day, part);
+                        print!("{}", answer);
+                    }
+                }
+            }
+            Err(e) => {
+                println!();
+                println!("{}", e);
+            }
+        }
+
+        match &result.check {
+            Some(Check::Failed(mismatches)) => {
+                for mismatch in mismatches {
+                    println!();
+                    if mismatch.expected.contains('\n') || mismatch.actual.contains('\n') {
+                        println!("Day {} part {}: expected", day, mismatch.part);
+                        print!("{}", mismatch.expected);
+                        println!("got");
+                        print!("{}", mismatch.actual);
+                    } else {
+                        println!(
+                            "Day {} part {}: expected `{}`, got `{}`",
+                            day, mismatch.part, mismatch.expected, mismatch.actual
+                        );
+                    }
+                }
+            }
+            Some(Check::Error(e)) => {
+                println!();
+                println!("{}", e);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns `value` as a JSON string literal.
+fn json_string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn json_stats(stats: &Stats) -> String {
+    format!(
+        "{{\"min_ns\": {}, \"median_ns\": {}, \"max_ns\": {}}}",
+        stats.min.as_nanos(),
+        stats.median.as_nanos(),
+        stats.max.as_nanos()
+    )
+}
+
+/// Prints the results as a JSON array with an object per day. Times are in nanoseconds.
+fn print_json(results: &[DayResult]) {
+    let days: Vec<String> = results
+        .iter()
+        .map(|result| {
+            let mut fields = vec![
+                format!("\"day\": {}", result.puzzle.day),
+                format!("\"title\": {}", json_string(result.puzzle.title)),
+            ];
+            match &result.result {
+                Ok(benchmark) => {
+                    fields.push(format!(
+                        "\"part1\": {}",
+                        json_string(&benchmark.answers.part1)
+                    ));
+                    fields.push(format!(
+                        "\"part2\": {}",
+                        json_string(&benchmark.answers.part2)
+                    ));
+                    fields.push(format!("\"runs\": {}", benchmark.runs));
+                    fields.push(format!("\"parse\": {}", json_stats(&benchmark.parse)));
+                    fields.push(format!("\"part1_time\": {}", json_stats(&benchmark.part1)));
+                    fields.push(format!("\"part2_time\": {}", json_stats(&benchmark.part2)));
+                    fields.push(format!("\"total\": {}", json_stats(&benchmark.total)));
+                }
+                Err(e) => fields.push(format!("\"error\": {}", json_string(&e.to_string()))),
+            }
+            let check = match &result.check {
+                None => None,
+                Some(Check::Passed) => Some("\"ok\"".to_owned()),
+                Some(Check::Failed(_)) => Some("\"failed\"".to_owned()),
+                Some(Check::Unknown) => Some("null".to_owned()),
+                Some(Check::Error(e)) => Some(json_string(&e.to_string())),
+            };
+            if let Some(check) = check {
+                fields.push(format!("\"check\": {}", check));
+            }
+            format!("  {{{}}}", fields.join(", "))
+        })
+        .collect();
+    println!("[\n{}\n]", days.join(",\n"));
+}
+
+fn main() {
+    let options = match parse_options(std::env::args().skip(1).collect()) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            std::process::exit(1);
+        }
+    };
+
+    let results: Vec<DayResult> = options
+        .puzzles
+        .iter()
+        .map(|&puzzle| {
+            let result = bench::run(puzzle, &options.source, options.runs);
+            let check = match &result {
+                Ok(benchmark) if options.check => Some(check(puzzle, &benchmark.answers)),
+                _ => None,
+            };
+            DayResult {
+                puzzle,
+                result,
+                check,
+            }
+        })
+        .collect();
+    if options.json {
+        print_json(&results);
+    } else {
+        print_results(&results, &options);
+    }
+
+    let failed = results.iter().any(|r| {
+        r.result.is_err() || matches!(r.check, Some(Check::Failed(_)) | Some(Check::Error(_)))
+    });
+    if failed {
+        std::process::exit(1);
+    }
+}
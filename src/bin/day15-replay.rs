@@ -0,0 +1,90 @@
+use aoc::days::day15::{self, Faction, Replay};
+use aoc::input::InputSource;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+const HELP: &str = "[enter] next round, p previous round, <n> go to round n, q quit";
+
+/// Steps through the battle of day 15 round by round. Takes the input or a scenario like the
+/// other binaries, optionally followed by the attack power of the elves.
+fn main() {
+    // If the scenario comes from stdin the commands have to come from the terminal
+    let commands: Box<dyn BufRead> = match InputSource::from_arg(std::env::args().nth(1).as_deref())
+    {
+        InputSource::Stdin => match File::open("/dev/tty") {
+            Ok(tty) => Box::new(BufReader::new(tty)),
+            Err(_) => {
+                eprintln!("Reading the input from stdin needs a terminal to read commands from");
+                std::process::exit(1);
+            }
+        },
+        _ => Box::new(BufReader::new(std::io::stdin())),
+    };
+
+    let scenario = aoc::input::from_args(15, day15::parse_scenario);
+    let mut world = scenario.world;
+    match std::env::args().nth(2).map(|arg| arg.parse()) {
+        None => {}
+        Some(Ok(attack_power)) => {
+            let rules = world
+                .rules()
+                .clone()
+                .with_attack_power(Faction::ELF, attack_power);
+            world = world.with_rules(rules);
+        }
+        Some(Err(_)) => {
+            eprintln!("Invalid attack power");
+            std::process::exit(1);
+        }
+    }
+
+    let factions = world.factions();
+    let replay = Replay::record(&world);
+    let last = replay.frames.len() - 1;
+    let mut current = 0;
+    let mut lines = commands.lines();
+    loop {
+        // Clear the terminal before drawing the next frame
+        print!("\x1b[2J\x1b[H");
+        println!("{}", replay.frames[current]);
+        if current == last {
+            let result = &replay.result;
+            match result.winner {
+                Some(winner) => println!(
+                    "{} wins after {} rounds with an outcome of {}",
+                    winner, result.rounds, result.outcome
+                ),
+                None => println!(
+                    "Stalemate after {} rounds with an outcome of {}",
+                    result.rounds, result.outcome
+                ),
+            }
+            for faction in factions.iter() {
+                println!("{} lost {} units", faction, result.deaths(*faction));
+            }
+            if let Some(expected) = scenario.expected_outcome {
+                println!("Expected an outcome of {}", expected);
+            }
+        }
+        println!("{}", HELP);
+
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            _ => break,
+        };
+        match line.trim() {
+            "" | "n" => current = (current + 1).min(last),
+            "p" => current = current.saturating_sub(1),
+            "q" => break,
+            round => {
+                if let Ok(round) = round.parse::<i32>() {
+                    current = replay
+                        .frames
+                        .iter()
+                        .position(|frame| frame.round >= round)
+                        .unwrap_or(last);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use aoc::days::day19;
+
+fn main() {
+    let input = aoc::input::from_args(19, day19::parse);
+    let (result1, result2) = day19::solve(&input);
+    println!("Result 1: {}", result1);
+    println!("Result 2: {}", result2);
+}
@@ -0,0 +1,8 @@
+use aoc::days::day20;
+
+fn main() {
+    let input = aoc::input::from_args(20, day20::parse);
+    let (result1, result2) = day20::solve(&input);
+    println!("Result 1: {}", result1);
+    println!("Result 2: {}", result2);
+}
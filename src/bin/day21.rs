@@ -0,0 +1,8 @@
+use aoc::days::day21;
+
+fn main() {
+    let input = aoc::input::from_args(21, day21::parse);
+    let (result1, result2) = day21::solve(&input);
+    println!("Result 1: {}", result1);
+    println!("Result 2: {}", result2);
+}
@@ -0,0 +1,8 @@
+use aoc::days::day22;
+
+fn main() {
+    let input = aoc::input::from_args(22, day22::parse);
+    let (result1, result2) = day22::solve(&input);
+    println!("Result 1: {}", result1);
+    println!("Result 2: {}", result2);
+}
@@ -0,0 +1,8 @@
+use aoc::days::day23;
+
+fn main() {
+    let input = aoc::input::from_args(23, day23::parse);
+    let (result1, result2) = day23::solve(&input);
+    println!("Result 1: {}", result1);
+    println!("Result 2: {}", result2);
+}
@@ -0,0 +1,8 @@
+use aoc::days::day24;
+
+fn main() {
+    let input = aoc::input::from_args(24, day24::parse);
+    let (result1, result2) = day24::solve(&input);
+    println!("Result 1: {}", result1);
+    println!("Result 2: {}", result2);
+}
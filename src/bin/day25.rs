@@ -0,0 +1,8 @@
+use aoc::days::day25;
+
+fn main() {
+    let input = aoc::input::from_args(25, day25::parse);
+    let (result1, result2) = day25::solve(&input);
+    println!("Result 1: {}", result1);
+    println!("Result 2: {}", result2);
+}
@@ -0,0 +1,249 @@
+//! Cycle detection for sequences of states `x0, x1 = step(x0), x2 = step(x1), ...` that
+//! eventually repeat, used to extrapolate simulations far beyond what can be simulated.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where a sequence starts repeating: the state at `start + length` equals the one at `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Returns the index of the first state equal to state `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// Returns how many full cycles lie between state `n` and `reduce(n)`.
+    pub fn repetitions(&self, n: usize) -> usize {
+        if n < self.start {
+            0
+        } else {
+            (n - self.start) / self.length
+        }
+    }
+}
+
+/// Finds the cycle with Brent's algorithm, which only keeps two states in memory but steps
+/// through the sequence a few times.
+pub fn brent<T, F>(initial: &T, mut step: F) -> Cycle
+where
+    T: Clone + Eq,
+    F: FnMut(&T) -> T,
+{
+    // Find the length by moving the hare ahead in growing powers of two until it meets the
+    // tortoise, which waits at the start of every power
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // Find the start with two states `length` apart
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Finds the cycle with Floyd's algorithm, which only keeps two states in memory.
+pub fn floyd<T, F>(initial: &T, mut step: F) -> Cycle
+where
+    T: Clone + Eq,
+    F: FnMut(&T) -> T,
+{
+    // Move the hare twice as fast as the tortoise until they meet somewhere in the cycle
+    let mut tortoise = step(initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let next = step(&hare);
+        hare = step(&next);
+    }
+
+    // The distance from the meeting point to the start of the cycle is the same as the distance
+    // from the initial state
+    let mut start = 0;
+    let mut tortoise = initial.clone();
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+    Cycle { start, length }
+}
+
+/// The states of a sequence up to the point where it starts repeating, possibly translated.
+#[derive(Debug, Clone)]
+pub struct Periodic<T> {
+    /// All states before the start of the second repetition of the cycle.
+    pub states: Vec<T>,
+    pub cycle: Cycle,
+    /// How far the state is translated each time the cycle repeats, see `detect_shifted`.
+    pub shift: i64,
+}
+
+impl<T> Periodic<T> {
+    /// Returns the state at index `n` together with how far it needs to be translated.
+    pub fn nth(&self, n: usize) -> (&T, i64) {
+        let repetitions = self.cycle.repetitions(n) as i64;
+        (&self.states[self.cycle.reduce(n)], self.shift * repetitions)
+    }
+}
+
+/// Finds the cycle by remembering every state, which takes more memory than `brent` but only
+/// steps through the sequence once and keeps the states for extrapolation.
+pub fn detect<T, F>(initial: T, step: F) -> Periodic<T>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(&T) -> T,
+{
+    detect_shifted(initial, step, |state| (state.clone(), 0))
+}
+
+/// Finds a cycle in a sequence of patterns that may move while repeating, like a glider.
+/// `normalize` splits a state into its shape and its position. The cycle ends when a shape
+/// repeats, and `Periodic::shift` tells how far the position moved in the meantime.
+pub fn detect_shifted<T, K, F, N>(initial: T, mut step: F, mut normalize: N) -> Periodic<T>
+where
+    K: Eq + Hash,
+    F: FnMut(&T) -> T,
+    N: FnMut(&T) -> (K, i64),
+{
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+    loop {
+        let (shape, position) = normalize(&state);
+        if let Some(&(start, start_position)) = seen.get(&shape) {
+            return Periodic {
+                cycle: Cycle {
+                    start,
+                    length: states.len() - start,
+                },
+                states,
+                shift: position - start_position,
+            };
+        }
+        seen.insert(shape, (states.len(), position));
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+/// Returns the state at index `n`, skipping ahead once the sequence repeats.
+pub fn nth<T, F>(initial: T, step: F, n: usize) -> T
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(&T) -> T,
+{
+    let (state, _) = nth_shifted(initial, step, |state| (state.clone(), 0), n);
+    state
+}
+
+/// Returns the state at index `n` as an earlier state and how far it needs to be translated,
+/// skipping ahead once the sequence repeats. See `detect_shifted`.
+pub fn nth_shifted<T, K, F, N>(initial: T, mut step: F, mut normalize: N, n: usize) -> (T, i64)
+where
+    T: Clone,
+    K: Eq + Hash,
+    F: FnMut(&T) -> T,
+    N: FnMut(&T) -> (K, i64),
+{
+    // Like `detect_shifted`, but stops early if state `n` comes before the cycle is complete
+    let mut seen = HashMap::new();
+    let mut states: Vec<T> = Vec::new();
+    let mut state = initial;
+    while states.len() < n {
+        let (shape, position) = normalize(&state);
+        if let Some(&(start, start_position)) = seen.get(&shape) {
+            let periodic = Periodic {
+                cycle: Cycle {
+                    start,
+                    length: states.len() - start,
+                },
+                states,
+                shift: position - start_position,
+            };
+            let (state, shift) = periodic.nth(n);
+            return (state.clone(), shift);
+        }
+        seen.insert(shape, (states.len(), position));
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+    (state, 0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn algorithms_agree() {
+        // 2, 5, 26, 17, 15, 6, 37, 50, 26, ... repeats from 26 with a length of 6
+        let step = |x: &u32| (x * x + 1) % 55;
+        let cycle = Cycle {
+            start: 2,
+            length: 6,
+        };
+        assert_eq!(brent(&2, step), cycle);
+        assert_eq!(floyd(&2, step), cycle);
+        assert_eq!(detect(2, step).cycle, cycle);
+        assert_eq!(nth(2, step, 1_000_000_002), 37);
+        assert_eq!(nth(2, step, 1), 5);
+    }
+
+    #[test]
+    fn shifted() {
+        // A pattern that moves two to the right every three steps
+        let step = |&(phase, position): &(u8, i64)| match phase {
+            2 => (0, position + 2),
+            _ => (phase + 1, position),
+        };
+        let normalize = |&(phase, position): &(u8, i64)| (phase, position);
+        let periodic = detect_shifted((0, 5), step, normalize);
+        assert_eq!(
+            periodic.cycle,
+            Cycle {
+                start: 0,
+                length: 3
+            }
+        );
+        assert_eq!(periodic.shift, 2);
+        assert_eq!(periodic.nth(10), (&(1, 5), 6));
+        assert_eq!(nth_shifted((0, 5), step, normalize, 10), ((1, 5), 6));
+    }
+}
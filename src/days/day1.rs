@@ -0,0 +1,61 @@
+use crate::parse::lines;
+use crate::{ParseError, Solution};
+use std::collections::HashSet;
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    let changes: Vec<i64> = lines(input).map(|l| l.parse()).collect::<Result<_, _>>()?;
+    if changes.is_empty() {
+        return Err(ParseError::new(
+            1,
+            None,
+            "expected at least one frequency change",
+        ));
+    }
+    Ok(changes)
+}
+
+pub fn part1(input: &[i64]) -> i64 {
+    // Sum the entries together
+    input.iter().sum()
+}
+
+pub fn part2(input: &[i64]) -> i64 {
+    // Iterate over all entries (forever) and record which states we've come across.
+    let mut values_seen = HashSet::<i64>::new();
+    let mut current_frequency_index: usize = 0;
+    let mut current_frequency = 0;
+    values_seen.insert(current_frequency);
+    loop {
+        current_frequency += input[current_frequency_index];
+        if values_seen.contains(&current_frequency) {
+            return current_frequency;
+        }
+        values_seen.insert(current_frequency);
+        current_frequency_index = (current_frequency_index + 1) % input.len();
+    }
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[i64]) -> (i64, i64) {
+    (part1(input), part2(input))
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i64>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
@@ -0,0 +1,87 @@
+use crate::parse::lines;
+use crate::{Bounds, Grid, ParseError, Point, Solution};
+
+/// A light with its initial position and velocity.
+pub type Light = (Point, Point);
+
+/// Returns the positions of all lights after the given number of seconds.
+pub fn positions_at(input: &[Light], seconds: i32) -> impl Iterator<Item = Point> + '_ {
+    input.iter().map(move |(p, v)| *p + *v * seconds)
+}
+
+/// Returns the bounds of all lights after the given number of seconds.
+pub fn bounds_at(input: &[Light], seconds: i32) -> Bounds {
+    Bounds::from_points(positions_at(input, seconds)).expect("no lights")
+}
+
+/// Iterates over the points to find the inflection point where the height no longer shrinks.
+pub fn find_message_time(input: &[Light]) -> i32 {
+    let mut height = bounds_at(input, 0).height();
+    let mut seconds = 1;
+    loop {
+        let current_height = bounds_at(input, seconds).height();
+        if current_height > height {
+            return seconds - 1;
+        }
+
+        height = current_height;
+        seconds += 1;
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Light>, ParseError> {
+    let regex =
+        regex::Regex::new(r"^position=<\s*(-?\d+),\s*(-?\d+)> velocity=<\s*(-?\d+),\s*(-?\d+)>$")
+            .unwrap();
+    let lights: Vec<Light> = lines(input)
+        .map(|l| {
+            let captures = l.captures(&regex, "position=<x, y> velocity=<x, y>")?;
+            Ok((
+                Point::new(captures.parse(1)?, captures.parse(2)?),
+                Point::new(captures.parse(3)?, captures.parse(4)?),
+            ))
+        })
+        .collect::<Result<_, _>>()?;
+    if lights.is_empty() {
+        return Err(ParseError::new(1, None, "expected at least one light"));
+    }
+    Ok(lights)
+}
+
+pub fn part1(input: &[Light]) -> String {
+    let seconds = find_message_time(input);
+    let mut grid = Grid::from_bounds(bounds_at(input, seconds), ' ');
+    for position in positions_at(input, seconds) {
+        grid[position] = '#';
+    }
+    grid.to_string()
+}
+
+pub fn part2(input: &[Light]) -> i32 {
+    find_message_time(input)
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[Light]) -> (String, i32) {
+    (part1(input), part2(input))
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Light>;
+    type Part1 = String;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
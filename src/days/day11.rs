@@ -0,0 +1,150 @@
+use crate::parse::lines;
+use crate::{Grid, ParseError, Point, Solution};
+
+pub fn power_level(x: i32, y: i32, grid_serial: i32) -> i32 {
+    let rack_id = x + 10;
+    let value = (rack_id * y + grid_serial) * rack_id;
+    let digit = (value % 1000 - value % 100) / 100;
+    digit - 5
+}
+
+pub fn compute_power_levels(width: usize, height: usize, grid_serial: i32) -> Grid<i32> {
+    Grid::from_fn(width, height, |p| power_level(p.x, p.y, grid_serial))
+}
+
+/// Returns for every possible top-left corner the total power of the square block of
+/// `block_size` cells.
+pub fn compute_power_level_blocks(block_size: usize, power_level_grid: &Grid<i32>) -> Grid<i32> {
+    let width = power_level_grid.width() - block_size + 1;
+    let height = power_level_grid.height() - block_size + 1;
+    let block_size = block_size as i32;
+    Grid::from_fn(width, height, |p| {
+        let mut total = 0;
+        for by in 0..block_size {
+            for bx in 0..block_size {
+                total += power_level_grid[Point::new(p.x + bx, p.y + by)];
+            }
+        }
+        total
+    })
+}
+
+pub fn find_max_block(grid_serial: i32) -> (i32, i32) {
+    let blocks = compute_power_level_blocks(3, &compute_power_levels(300, 300, grid_serial));
+    blocks
+        .iter()
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+        .map(|(p, _)| (p.x, p.y))
+        .unwrap()
+}
+
+pub fn find_max(grid_serial: i32) -> (i32, i32, i32) {
+    let size = 300;
+    let mut block_size = 3;
+    let power_levels = compute_power_levels(size as usize, size as usize, grid_serial);
+    let mut blocks = compute_power_level_blocks(block_size as usize, &power_levels);
+
+    let mut max_location = (0, 0, 0);
+    let mut max_value = 0;
+    loop {
+        // Find the max
+        for y in 0..size - (block_size - 1) {
+            for x in 0..size - (block_size - 1) {
+                let block = blocks[Point::new(x, y)];
+                if block > max_value {
+                    max_value = block;
+                    max_location = (x, y, block_size);
+                }
+            }
+        }
+
+        // Increase the block size
+        block_size += 1;
+        if block_size > size {
+            return max_location;
+        }
+
+        // Increase the blocks range
+        for y in 0..size - (block_size - 1) {
+            for x in 0..size - (block_size - 1) {
+                let mut total = 0;
+                for by in 0..block_size - 1 {
+                    total += power_levels[Point::new(x + block_size - 1, y + by)];
+                }
+                for bx in 0..block_size {
+                    total += power_levels[Point::new(x + bx, y + block_size - 1)];
+                }
+                blocks[Point::new(x, y)] += total;
+            }
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<i32, ParseError> {
+    lines(input)
+        .next()
+        .ok_or_else(|| ParseError::new(1, None, "expected the grid serial number"))?
+        .trim()
+        .parse()
+}
+
+pub fn part1(grid_serial: &i32) -> String {
+    let (x, y) = find_max_block(*grid_serial);
+    format!("{},{}", x, y)
+}
+
+pub fn part2(grid_serial: &i32) -> String {
+    let (x, y, size) = find_max(*grid_serial);
+    format!("{},{},{}", x, y, size)
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &i32) -> (String, String) {
+    (part1(input), part2(input))
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    /// The serial number of the fuel cell grid.
+    type Input = i32;
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn power_levels() {
+        assert_eq!(power_level(3, 5, 8), 4);
+        assert_eq!(power_level(122, 79, 57), -5);
+        assert_eq!(power_level(217, 196, 39), 0);
+        assert_eq!(power_level(101, 153, 71), 4);
+    }
+
+    #[test]
+    fn max_blocks() {
+        assert_eq!(find_max_block(18), (33, 45));
+        assert_eq!(find_max_block(42), (21, 61));
+    }
+
+    #[test]
+    fn max_blocks_dynamic() {
+        assert_eq!(find_max(18), (90, 269, 16));
+        assert_eq!(find_max(42), (232, 251, 12));
+    }
+}
@@ -0,0 +1,358 @@
+use crate::parse::{lines, Span};
+use crate::{cycle, ParseError, Solution};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum PotState {
+    Plant,
+    Empty,
+}
+
+/// Parses a row of pots where `#` is a plant and `.` is empty.
+fn parse_pots(span: Span) -> Result<Vec<PotState>, ParseError> {
+    span.char_indices()
+        .map(|(offset, c)| match c {
+            '#' => Ok(PotState::Plant),
+            '.' => Ok(PotState::Empty),
+            _ => Err(span.error_at(offset, format!("expected `#` or `.`, found `{}`", c))),
+        })
+        .collect()
+}
+
+impl std::fmt::Display for PotState {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            PotState::Plant => write!(f, "#"),
+            PotState::Empty => write!(f, "."),
+        }
+    }
+}
+
+/// The widest neighbourhood a rule can look at, which keeps the lookup table at 2 MB.
+pub const MAX_RULE_WIDTH: usize = 21;
+
+pub struct Rule {
+    /// The pots around the one the rule applies to, of odd length.
+    pub pattern: Vec<PotState>,
+    pub result: PotState,
+}
+
+impl std::fmt::Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        for pot in self.pattern.iter() {
+            write!(f, "{}", pot)?;
+        }
+        write!(f, " => {}", self.result)
+    }
+}
+
+/// All rules of the same width as a lookup table from neighbourhood to the next state of the pot.
+pub struct Rules {
+    width: usize,
+    /// Indexed by the neighbourhood as a bitmask with the leftmost pot in the highest bit.
+    table: Vec<PotState>,
+}
+
+impl Rules {
+    /// Builds the table for rules that are all `width` wide. Neighbourhoods without a rule leave
+    /// the pot empty.
+    ///
+    /// # Panics
+    ///
+    /// If `width` is even or larger than `MAX_RULE_WIDTH`, or a pattern has a different width.
+    pub fn new(width: usize, rules: &[Rule]) -> Rules {
+        assert!(width % 2 == 1 && width <= MAX_RULE_WIDTH);
+        let mut table = vec![PotState::Empty; 1 << width];
+        for rule in rules {
+            assert_eq!(rule.pattern.len(), width);
+            table[bitmask(&rule.pattern)] = rule.result;
+        }
+        Rules { width, table }
+    }
+
+    /// Returns how many pots on each side of a pot influence it.
+    pub fn radius(&self) -> usize {
+        self.width / 2
+    }
+
+    fn apply(&self, neighbourhood: u32) -> bool {
+        self.table[neighbourhood as usize] == PotState::Plant
+    }
+}
+
+fn bitmask(pots: &[PotState]) -> usize {
+    pots.iter().fold(0, |mask, &pot| {
+        (mask << 1) | (pot == PotState::Plant) as usize
+    })
+}
+
+/// A row of pots stored as bits, trimmed so that the first bit and the last word are not empty.
+#[derive(Clone)]
+pub struct State {
+    pots: Vec<u64>,
+    /// The position of the first bit.
+    offset: i64,
+}
+
+impl State {
+    pub fn new(initial_state: &[PotState]) -> State {
+        let mut builder = StateBuilder::new(0);
+        for &pot in initial_state {
+            builder.push(pot == PotState::Plant);
+        }
+        builder.build()
+    }
+
+    /// Returns the number of bits up to the last plant.
+    fn len(&self) -> usize {
+        match self.pots.last() {
+            Some(last) => self.pots.len() * 64 - last.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    fn bit(&self, index: usize) -> bool {
+        self.pots
+            .get(index / 64)
+            .is_some_and(|word| word & (1 << (index % 64)) != 0)
+    }
+
+    /// Returns the state of the pot at `position`.
+    pub fn get(&self, position: i64) -> PotState {
+        if position >= self.offset && self.bit((position - self.offset) as usize) {
+            PotState::Plant
+        } else {
+            PotState::Empty
+        }
+    }
+
+    pub fn iterate(&self, rules: &Rules) -> State {
+        // The new pot at `offset - radius + i` depends on the old bits `i - 2 * radius..=i`,
+        // which are shifted through the neighbourhood one at a time
+        let radius = rules.radius();
+        let mask = (1 << rules.width) - 1;
+        let mut neighbourhood = 0u32;
+        let mut builder = StateBuilder::new(self.offset - radius as i64);
+        for i in 0..self.len() + 2 * radius {
+            neighbourhood = ((neighbourhood << 1) | self.bit(i) as u32) & mask;
+            builder.push(rules.apply(neighbourhood));
+        }
+        builder.build()
+    }
+
+    /// Returns the state after `n` generations. Rows tend to end up in a pattern that repeats
+    /// while moving along, which is detected to skip the remaining generations.
+    pub fn iterate_n(&self, rules: &Rules, n: usize) -> State {
+        let (mut state, shift) = cycle::nth_shifted(
+            self.clone(),
+            |state| state.iterate(rules),
+            |state| (state.pots.clone(), state.offset),
+            n,
+        );
+        state.offset += shift;
+        state
+    }
+
+    pub fn plant_sum(&self) -> i64 {
+        (0..self.len())
+            .filter(|&i| self.bit(i))
+            .map(|i| i as i64 + self.offset)
+            .sum()
+    }
+}
+
+impl std::fmt::Display for State {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        for i in 0..self.len() {
+            write!(f, "{}", if self.bit(i) { '#' } else { '.' })?
+        }
+        Ok(())
+    }
+}
+
+/// Collects pots from left to right into a trimmed `State`.
+struct StateBuilder {
+    pots: Vec<u64>,
+    len: usize,
+    offset: i64,
+}
+
+impl StateBuilder {
+    fn new(offset: i64) -> StateBuilder {
+        StateBuilder {
+            pots: Vec::new(),
+            len: 0,
+            offset,
+        }
+    }
+
+    fn push(&mut self, plant: bool) {
+        if self.len == 0 && !plant {
+            self.offset += 1;
+            return;
+        }
+        if self.len % 64 == 0 {
+            self.pots.push(0);
+        }
+        if plant {
+            self.pots[self.len / 64] |= 1 << (self.len % 64);
+        }
+        self.len += 1;
+    }
+
+    fn build(mut self) -> State {
+        while let Some(0) = self.pots.last() {
+            self.pots.pop();
+        }
+        State {
+            pots: self.pots,
+            offset: self.offset,
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<(State, Rules), ParseError> {
+    let mut lines_iter = lines(input);
+    let first_line = lines_iter
+        .next()
+        .ok_or_else(|| ParseError::new(1, None, "expected the initial state"))?;
+    let initial_state = State::new(&parse_pots(first_line.strip_prefix("initial state: ")?)?);
+
+    // Skip white line
+    if let Some(line) = lines_iter.next() {
+        if !line.is_empty() {
+            return Err(line.error("expected an empty line"));
+        }
+    }
+
+    let mut width = None;
+    let mut rules = Vec::new();
+    for line in lines_iter {
+        let mut parts = line.split(" => ");
+        let (pattern, result) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(pattern), Some(result), None) if result.text.len() == 1 => (pattern, result),
+            _ => return Err(line.error("expected `LLCRR => N`")),
+        };
+        let rule = Rule {
+            pattern: parse_pots(pattern)?,
+            result: parse_pots(result)?[0],
+        };
+        let expected = *width.get_or_insert(rule.pattern.len());
+        if rule.pattern.len() != expected {
+            return Err(pattern.error(format!("expected a pattern of {} pots", expected)));
+        } else if expected % 2 == 0 || expected > MAX_RULE_WIDTH {
+            return Err(pattern.error(format!(
+                "expected an odd number of pots up to {}",
+                MAX_RULE_WIDTH
+            )));
+        } else if rule.result == PotState::Plant
+            && rule.pattern.iter().all(|&p| p == PotState::Empty)
+        {
+            return Err(line.error("plants can't grow without any plants nearby"));
+        }
+        rules.push(rule);
+    }
+    Ok((initial_state, Rules::new(width.unwrap_or(1), &rules)))
+}
+
+pub fn part1((initial_state, rules): &(State, Rules)) -> i64 {
+    initial_state.iterate_n(rules, 20).plant_sum()
+}
+
+pub fn part2((initial_state, rules): &(State, Rules)) -> i64 {
+    initial_state.iterate_n(rules, 50_000_000_000).plant_sum()
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &(State, Rules)) -> (i64, i64) {
+    (part1(input), part2(input))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = (State, Rules);
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = parse(
+            "initial state: #..#.#..##......###...###
+
+...## => #
+..#.. => #
+.#... => #
+.#.#. => #
+.#.## => #
+.##.. => #
+.#### => #
+#.#.# => #
+#.### => #
+##.#. => #
+##.## => #
+###.. => #
+###.# => #
+####. => #",
+        )
+        .unwrap();
+        let (initial_state, rules) = &input;
+        let state = initial_state.iterate_n(rules, 20);
+        assert_eq!(state.to_string(), "#....##....#####...#######....#.#..##");
+        assert_eq!(state.get(-2), PotState::Plant);
+        assert_eq!(part1(&input), 325);
+    }
+
+    #[test]
+    fn other_widths() {
+        // Rule 90 grows a Sierpinski triangle with 2^k plants after generation n, where k is
+        // the number of ones in n
+        let (initial_state, rules) = parse(
+            "initial state: #
+
+#.. => #
+..# => #
+##. => #
+.## => #",
+        )
+        .unwrap();
+        for &n in [1, 6, 63, 64, 100].iter() {
+            let state = initial_state.iterate_n(&rules, n);
+            assert_eq!(state.len(), 2 * n + 1);
+            let plants = (0..state.len()).filter(|&i| state.bit(i)).count();
+            assert_eq!(plants, 1 << n.count_ones());
+        }
+
+        // A glider that moves three pots to the right every generation
+        let (initial_state, rules) = parse(
+            "initial state: ##
+
+##..... => #
+#...... => #",
+        )
+        .unwrap();
+        let state = initial_state.iterate_n(&rules, 1_000_000);
+        assert_eq!(state.to_string(), "##");
+        assert_eq!(state.plant_sum(), 6_000_001);
+
+        assert!(parse("initial state: #\n\n.... => #").is_err());
+        assert!(parse("initial state: #\n\n..... => #").is_err());
+        assert!(parse("initial state: #\n\n..#.. => #\n.#. => #").is_err());
+    }
+}
@@ -0,0 +1,508 @@
+use crate::parse::end_of_input;
+use crate::search;
+use crate::{Direction, Grid, ParseError, Point, Solution};
+use std::collections::VecDeque;
+
+pub enum Track {
+    Horizontal,
+    Vertical,
+    Crossing,
+    CurveRight,
+    CurveLeft,
+}
+
+impl std::fmt::Display for Track {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            Track::Horizontal => write!(f, "-"),
+            Track::Vertical => write!(f, "|"),
+            Track::Crossing => write!(f, "+"),
+            Track::CurveRight => write!(f, "/"),
+            Track::CurveLeft => write!(f, "\\"),
+        }
+    }
+}
+
+impl Track {
+    /// Returns the directions a cart moving in `direction` can leave the track in, depending on
+    /// how it turns at an intersection. Returns none if the track does not run that way.
+    fn exits(&self, direction: Direction) -> Vec<Direction> {
+        let horizontal = direction == Direction::Left || direction == Direction::Right;
+        match self {
+            Track::Horizontal if horizontal => vec![direction],
+            Track::Vertical if !horizontal => vec![direction],
+            Track::Horizontal | Track::Vertical => vec![],
+            Track::Crossing => vec![direction.turn_left(), direction, direction.turn_right()],
+            Track::CurveRight | Track::CurveLeft => vec![self.curve(direction)],
+        }
+    }
+
+    /// Returns the direction a cart moving in `direction` leaves a curve in.
+    fn curve(&self, direction: Direction) -> Direction {
+        match (self, direction) {
+            (Track::CurveLeft, Direction::Up) => Direction::Left,
+            (Track::CurveLeft, Direction::Right) => Direction::Down,
+            (Track::CurveLeft, Direction::Left) => Direction::Up,
+            (Track::CurveLeft, Direction::Down) => Direction::Right,
+            (Track::CurveRight, Direction::Up) => Direction::Right,
+            (Track::CurveRight, Direction::Right) => Direction::Up,
+            (Track::CurveRight, Direction::Left) => Direction::Down,
+            (Track::CurveRight, Direction::Down) => Direction::Left,
+            _ => direction,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TurnDirection {
+    Left,
+    Straight,
+    Right,
+}
+
+/// Decides which way a cart goes at an intersection.
+pub trait TurnPolicy {
+    fn turn(&self, cart: &Cart) -> TurnDirection;
+}
+
+/// Any function of the cart is a policy, e.g. to let some carts always go straight.
+impl<F: Fn(&Cart) -> TurnDirection> TurnPolicy for F {
+    fn turn(&self, cart: &Cart) -> TurnDirection {
+        self(cart)
+    }
+}
+
+/// Goes through the directions in turn at consecutive intersections.
+pub struct Rotation(Vec<TurnDirection>);
+
+impl Rotation {
+    /// # Panics
+    ///
+    /// If `turns` is empty.
+    pub fn new(turns: Vec<TurnDirection>) -> Rotation {
+        assert!(!turns.is_empty(), "a rotation needs at least one direction");
+        Rotation(turns)
+    }
+}
+
+impl Default for Rotation {
+    /// The rotation of the puzzle: left, then straight, then right, then left again.
+    fn default() -> Rotation {
+        Rotation::new(vec![
+            TurnDirection::Left,
+            TurnDirection::Straight,
+            TurnDirection::Right,
+        ])
+    }
+}
+
+impl TurnPolicy for Rotation {
+    fn turn(&self, cart: &Cart) -> TurnDirection {
+        self.0[cart.crossings % self.0.len()]
+    }
+}
+
+#[derive(Clone)]
+pub struct Cart {
+    /// The index of the cart in reading order at the start.
+    pub id: usize,
+    orientation: Direction,
+    /// The number of intersections the cart went through.
+    crossings: usize,
+    pub position: Point,
+}
+
+impl Cart {
+    pub fn new(id: usize, orientation: Direction, position: Point) -> Cart {
+        Cart {
+            id,
+            orientation,
+            crossings: 0,
+            position,
+        }
+    }
+
+    pub fn orientation(&self) -> Direction {
+        self.orientation
+    }
+
+    pub fn crossings(&self) -> usize {
+        self.crossings
+    }
+
+    fn update_position(&mut self) {
+        self.position = self.position.step(self.orientation);
+    }
+
+    fn update_orientation<P: TurnPolicy>(&mut self, track: &Track, policy: &P) {
+        match track {
+            Track::CurveLeft | Track::CurveRight => {
+                self.orientation = track.curve(self.orientation);
+            }
+            Track::Crossing => {
+                self.orientation = match policy.turn(self) {
+                    TurnDirection::Left => self.orientation.turn_left(),
+                    TurnDirection::Straight => self.orientation,
+                    TurnDirection::Right => self.orientation.turn_right(),
+                };
+                self.crossings += 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+pub type Tracks = Grid<Option<Track>>;
+
+/// Two carts that crashed into each other and were removed from the tracks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Collision {
+    /// The tick during which the carts crashed, starting at 1.
+    pub tick: usize,
+    pub position: Point,
+    /// The ids of the cart that moved and the cart it ran into.
+    pub carts: (usize, usize),
+}
+
+/// The number of ticks after which a simulation stops yielding collisions by default.
+pub const DEFAULT_TICK_LIMIT: usize = 1_000_000;
+
+/// Runs the carts over the tracks tick by tick. As an iterator it keeps ticking and yields every
+/// collision until fewer than two carts are left or the tick limit is reached, since carts may
+/// never collide. Collisions of ticks done by calling `tick` directly are not yielded.
+pub struct Simulation<'a, P> {
+    tracks: &'a Tracks,
+    carts: Vec<Cart>,
+    policy: P,
+    tick: usize,
+    tick_limit: usize,
+    /// The collisions of the last tick.
+    collisions: Vec<Collision>,
+    /// The collisions the iterator has not yielded yet.
+    pending: VecDeque<Collision>,
+}
+
+impl<'a, P: TurnPolicy> Simulation<'a, P> {
+    /// # Panics
+    ///
+    /// `tick` panics if a cart runs off the tracks, which `parse` makes sure cannot happen.
+    pub fn new(tracks: &'a Tracks, carts: Vec<Cart>, policy: P) -> Simulation<'a, P> {
+        Simulation {
+            tracks,
+            carts,
+            policy,
+            tick: 0,
+            tick_limit: DEFAULT_TICK_LIMIT,
+            collisions: Vec::new(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Sets the number of ticks after which the iterator stops.
+    pub fn with_tick_limit(mut self, tick_limit: usize) -> Simulation<'a, P> {
+        self.tick_limit = tick_limit;
+        self
+    }
+
+    /// Returns the carts that are still on the tracks.
+    pub fn carts(&self) -> &[Cart] {
+        &self.carts
+    }
+
+    /// Returns the number of ticks done.
+    pub fn ticks(&self) -> usize {
+        self.tick
+    }
+
+    /// Moves every cart once, in reading order of their positions, and removes the carts that
+    /// crash. Returns the collisions that occurred.
+    pub fn tick(&mut self) -> &[Collision] {
+        self.tick += 1;
+        self.collisions.clear();
+        self.carts.sort_by_key(|cart| cart.position);
+
+        let mut crashed = vec![false; self.carts.len()];
+        for i in 0..self.carts.len() {
+            if crashed[i] {
+                continue;
+            }
+            self.carts[i].update_position();
+
+            let position = self.carts[i].position;
+            let other = (0..self.carts.len())
+                .find(|&j| j != i && !crashed[j] && self.carts[j].position == position);
+            if let Some(j) = other {
+                crashed[i] = true;
+                crashed[j] = true;
+                self.collisions.push(Collision {
+                    tick: self.tick,
+                    position,
+                    carts: (self.carts[i].id, self.carts[j].id),
+                });
+                continue;
+            }
+
+            let track = self.tracks[position]
+                .as_ref()
+                .expect("a cart ran off the tracks");
+            self.carts[i].update_orientation(track, &self.policy);
+        }
+
+        let mut crashed = crashed.into_iter();
+        self.carts.retain(|_| !crashed.next().unwrap());
+        &self.collisions
+    }
+
+    /// Renders the tracks with the carts on them, marking the collisions of the last tick with
+    /// an `X`.
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+        for (y, row) in self.tracks.rows().enumerate() {
+            for (x, track) in row.iter().enumerate() {
+                let position = Point::new(x as i32, y as i32);
+                let cart = self.carts.iter().find(|cart| cart.position == position);
+                if self.collisions.iter().any(|c| c.position == position) {
+                    output.push('X');
+                } else if let Some(cart) = cart {
+                    output.push(match cart.orientation {
+                        Direction::Up => '^',
+                        Direction::Down => 'v',
+                        Direction::Left => '<',
+                        Direction::Right => '>',
+                    });
+                } else if let Some(track) = track {
+                    output += &track.to_string();
+                } else {
+                    output.push(' ');
+                }
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+impl<'a, P: TurnPolicy> Iterator for Simulation<'a, P> {
+    type Item = Collision;
+
+    fn next(&mut self) -> Option<Collision> {
+        while self.pending.is_empty() {
+            if self.carts.len() < 2 || self.tick >= self.tick_limit {
+                return None;
+            }
+            self.tick();
+            self.pending.extend(self.collisions.iter());
+        }
+        self.pending.pop_front()
+    }
+}
+
+pub fn parse(input: &str) -> Result<(Tracks, Vec<Cart>), ParseError> {
+    let mut carts = Vec::new();
+    let world = Grid::parse_padded(input, ' ', "track", |position, c| {
+        let orientation = match c {
+            '>' => Some(Direction::Right),
+            '<' => Some(Direction::Left),
+            '^' => Some(Direction::Up),
+            'v' => Some(Direction::Down),
+            _ => None,
+        };
+        if let Some(orientation) = orientation {
+            carts.push(Cart::new(carts.len(), orientation, position));
+        }
+
+        match c {
+            '/' => Some(Some(Track::CurveRight)),
+            '|' => Some(Some(Track::Vertical)),
+            '\\' => Some(Some(Track::CurveLeft)),
+            '-' => Some(Some(Track::Horizontal)),
+            '+' => Some(Some(Track::Crossing)),
+            '^' => Some(Some(Track::Vertical)),
+            '>' => Some(Some(Track::Horizontal)),
+            'v' => Some(Some(Track::Vertical)),
+            '<' => Some(Some(Track::Horizontal)),
+            ' ' => Some(None),
+            _ => None,
+        }
+    })?;
+
+    if carts.len() < 2 {
+        return Err(ParseError::new(
+            end_of_input(input),
+            None,
+            "expected at least two carts",
+        ));
+    }
+
+    if let Some(position) = find_dead_end(&world, &carts) {
+        return Err(ParseError::new(
+            position.y as usize + 1,
+            Some(position.x as usize + 1),
+            "a cart can run off the tracks here",
+        ));
+    }
+
+    Ok((world, carts))
+}
+
+/// Follows the carts along every way they could turn at intersections and returns the first
+/// position, in reading order, from which a cart can run off the tracks.
+fn find_dead_end(tracks: &Tracks, carts: &[Cart]) -> Option<Point> {
+    // The ways a cart at a position heading in a direction can go on after its next move
+    let next = |(position, direction): (Point, Direction)| -> Vec<(Point, Direction)> {
+        let next = position.step(direction);
+        match tracks.get(next) {
+            Some(Some(track)) => track
+                .exits(direction)
+                .into_iter()
+                .map(|exit| (next, exit))
+                .collect(),
+            _ => Vec::new(),
+        }
+    };
+    let starts = carts.iter().map(|cart| (cart.position, cart.orientation));
+    let paths = search::bfs_multi(starts, next);
+    paths
+        .iter()
+        .filter(|(state, _)| next(**state).is_empty())
+        .map(|(state, _)| state.0)
+        .min()
+}
+
+/// Returns the position of the first collision.
+pub fn part1((tracks, carts): &(Tracks, Vec<Cart>)) -> String {
+    let mut simulation = Simulation::new(tracks, carts.clone(), Rotation::default());
+    let collision = simulation
+        .next()
+        .expect("no carts collided within the tick limit");
+    collision.position.to_string()
+}
+
+/// Returns the position of the last cart left after all others crashed.
+pub fn part2((tracks, carts): &(Tracks, Vec<Cart>)) -> String {
+    let mut simulation = Simulation::new(tracks, carts.clone(), Rotation::default());
+    simulation.by_ref().for_each(drop);
+    match simulation.carts() {
+        [cart] => cart.position.to_string(),
+        [] => panic!("all carts crashed"),
+        _ => panic!("more than one cart is left after the tick limit"),
+    }
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &(Tracks, Vec<Cart>)) -> (String, String) {
+    (part1(input), part2(input))
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = (Tracks, Vec<Cart>);
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn trim_lines(text: &str) -> String {
+        text.lines()
+            .map(|line| line.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn first_collision() {
+        let input = parse(
+            r"/->-\
+|   |  /----\
+| /-+--+-\  |
+| | |  | v  |
+\-+-/  \-+--/
+  \------/",
+        )
+        .unwrap();
+        let (tracks, carts) = &input;
+        let mut simulation = Simulation::new(tracks, carts.clone(), Rotation::default());
+        simulation.tick();
+        assert_eq!(
+            trim_lines(&simulation.render()),
+            r"/-->\
+|   |  /----\
+| /-+--+-\  |
+| | |  | |  |
+\-+-/  \->--/
+  \------/"
+        );
+
+        let collision = simulation.next().unwrap();
+        assert_eq!(collision.tick, 14);
+        assert_eq!(collision.carts, (0, 1));
+        assert_eq!(
+            trim_lines(&simulation.render()),
+            r"/---\
+|   |  /----\
+| /-+--+-\  |
+| | |  X |  |
+\-+-/  \-+--/
+  \------/"
+        );
+        assert_eq!(part1(&input), "7,3");
+
+        // Going straight at every intersection the carts never meet
+        let straight = |_: &Cart| TurnDirection::Straight;
+        let mut simulation = Simulation::new(tracks, carts.clone(), straight);
+        for _ in 0..100 {
+            assert!(simulation.tick().is_empty());
+        }
+        assert_eq!(simulation.with_tick_limit(1000).next(), None);
+    }
+
+    #[test]
+    fn last_cart() {
+        let input = parse(
+            r"/>-<\
+|   |
+| /<+-\
+| | | v
+\>+</ |
+  |   ^
+  \<->/",
+        )
+        .unwrap();
+        let (tracks, carts) = &input;
+        let collisions: Vec<Collision> =
+            Simulation::new(tracks, carts.clone(), Rotation::default()).collect();
+        let positions: Vec<String> = collisions.iter().map(|c| c.position.to_string()).collect();
+        assert_eq!(positions, vec!["2,0", "2,4", "6,4", "2,4"]);
+        assert_eq!(part2(&input), "6,4");
+    }
+
+    #[test]
+    fn off_the_tracks() {
+        let error = parse("->- -<").err().unwrap();
+        assert_eq!((error.line, error.column), (1, Some(3)));
+        let error = parse("<>").err().unwrap();
+        assert_eq!((error.line, error.column), (1, Some(1)));
+        // The carts only run off the tracks if they turn at the intersection
+        let error = parse("/->-\\\n|   |\n\\-+</\n  |").err().unwrap();
+        assert_eq!((error.line, error.column), (3, Some(3)));
+        // A horizontal track can't be entered from above
+        let error = parse("v\n-\n|\n^").err().unwrap();
+        assert_eq!((error.line, error.column), (1, Some(1)));
+    }
+}
@@ -0,0 +1,124 @@
+use crate::parse::lines;
+use crate::{ParseError, Solution};
+use std::collections::VecDeque;
+
+pub fn digits(count: usize) -> Vec<usize> {
+    let mut value = count;
+    let mut result = Vec::new();
+    loop {
+        let digit = value % 10;
+        result.push(digit);
+        value = (value - digit) / 10;
+
+        if value == 0 {
+            break;
+        }
+    }
+
+    result.reverse();
+    result
+}
+
+fn compare_end(scores: &VecDeque<usize>, request: &[usize]) -> bool {
+    let size = request.len();
+    if scores.len() < size {
+        return false;
+    }
+
+    let begin = scores.len() - size;
+    scores.range(begin..).eq(request.iter())
+}
+
+/// Keeps creating new recipes and passes every new score to `on_recipe` until it returns `true`.
+pub fn create_recipes<F: FnMut(&VecDeque<usize>) -> bool>(mut on_recipe: F) {
+    let mut scoreboard = VecDeque::new();
+    scoreboard.push_back(3usize);
+    scoreboard.push_back(7usize);
+
+    let mut elves = [0usize, 1usize];
+
+    loop {
+        // Compute sum of current recipes
+        let sum = elves.iter().fold(0usize, |state, current_recipe| {
+            state + scoreboard[*current_recipe]
+        });
+
+        // Append the digits to the scoreboard
+        let digits = digits(sum);
+        for digit in digits.into_iter() {
+            scoreboard.push_back(digit);
+            if on_recipe(&scoreboard) {
+                return;
+            }
+        }
+
+        // Update current recipes
+        for current_recipe in elves.iter_mut() {
+            *current_recipe =
+                (*current_recipe + scoreboard[*current_recipe] + 1) % scoreboard.len();
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<usize, ParseError> {
+    lines(input)
+        .next()
+        .ok_or_else(|| ParseError::new(1, None, "expected the number of recipes"))?
+        .trim()
+        .parse()
+}
+
+pub fn part1(&input: &usize) -> String {
+    let mut result = String::new();
+    create_recipes(|scoreboard| {
+        if scoreboard.len() == input + 10 {
+            for score in scoreboard.range(input..input + 10) {
+                result.push_str(&score.to_string());
+            }
+            true
+        } else {
+            false
+        }
+    });
+    result
+}
+
+pub fn part2(&input: &usize) -> usize {
+    let input_digits = digits(input);
+    let mut result = 0;
+    create_recipes(|scoreboard| {
+        if compare_end(scoreboard, &input_digits) {
+            result = scoreboard.len() - input_digits.len();
+            true
+        } else {
+            false
+        }
+    });
+    result
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &usize) -> (String, usize) {
+    (part1(input), part2(input))
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    /// The number of recipes.
+    type Input = usize;
+    type Part1 = String;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
@@ -0,0 +1,914 @@
+use crate::battle;
+use crate::parse::{lines, Span};
+use crate::search::GridBfs;
+use crate::{Grid, ParseError, Point, Solution};
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// The side a unit fights for, identified by the capital letter that marks its units on the map.
+/// Every faction fights all others.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub struct Faction(pub char);
+
+impl Faction {
+    pub const ELF: Faction = Faction('E');
+    pub const GOBLIN: Faction = Faction('G');
+}
+
+impl Display for Faction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The strength of the units of a faction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub health_points: i32,
+    pub attack_power: i32,
+}
+
+impl Default for Stats {
+    fn default() -> Stats {
+        Stats {
+            health_points: 200,
+            attack_power: 3,
+        }
+    }
+}
+
+/// The stats of every faction. Factions without stats of their own use the default stats.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Rules {
+    stats: Vec<(Faction, Stats)>,
+}
+
+impl Rules {
+    pub fn stats(&self, faction: Faction) -> Stats {
+        self.stats
+            .iter()
+            .find(|(f, _)| *f == faction)
+            .map(|(_, stats)| *stats)
+            .unwrap_or_default()
+    }
+
+    pub fn set_stats(&mut self, faction: Faction, stats: Stats) {
+        match self.stats.iter_mut().find(|(f, _)| *f == faction) {
+            Some((_, s)) => *s = stats,
+            None => self.stats.push((faction, stats)),
+        }
+    }
+
+    pub fn with_attack_power(mut self, faction: Faction, attack_power: i32) -> Rules {
+        let stats = Stats {
+            attack_power,
+            ..self.stats(faction)
+        };
+        self.set_stats(faction, stats);
+        self
+    }
+}
+
+#[derive(Clone)]
+struct Unit {
+    /// The index of the unit in reading order at the start of the battle.
+    id: usize,
+    faction: Faction,
+    health_points: i32,
+    position: Point,
+}
+
+impl Unit {
+    fn new(id: usize, faction: Faction, health_points: i32, position: Point) -> Unit {
+        Unit {
+            id,
+            faction,
+            health_points,
+            position,
+        }
+    }
+
+    fn is_alive(&self) -> bool {
+        self.health_points > 0
+    }
+
+    fn is_dead(&self) -> bool {
+        self.health_points <= 0
+    }
+
+    fn status(&self) -> UnitStatus {
+        UnitStatus {
+            id: self.id,
+            faction: self.faction,
+            position: self.position,
+            health_points: self.health_points,
+        }
+    }
+}
+
+/// A unit as it is at some point during the battle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitStatus {
+    pub id: usize,
+    pub faction: Faction,
+    pub position: Point,
+    pub health_points: i32,
+}
+
+/// Something that happened during the battle. Units are identified by their index in reading
+/// order at the start of the battle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Move {
+        unit: usize,
+        from: Point,
+        to: Point,
+    },
+    Attack {
+        attacker: usize,
+        target: usize,
+        damage: i32,
+        /// The health points the target has left.
+        health_points: i32,
+    },
+    Death {
+        unit: usize,
+        position: Point,
+    },
+    /// A full round has been fought. Lists the units still alive in reading order.
+    RoundEnd {
+        round: i32,
+        units: Vec<UnitStatus>,
+    },
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            Event::Move { unit, from, to } => write!(f, "#{} moves from {} to {}", unit, from, to),
+            Event::Attack {
+                attacker,
+                target,
+                damage,
+                health_points,
+            } => write!(
+                f,
+                "#{} hits #{} for {}, {} left",
+                attacker, target, damage, health_points
+            ),
+            Event::Death { unit, position } => write!(f, "#{} dies at {}", unit, position),
+            Event::RoundEnd { round, units } => {
+                write!(f, "round {} ends:", round)?;
+                for u in units {
+                    write!(f, " #{} {}({})", u.id, u.faction, u.health_points)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Tile {
+    Wall,
+    Empty,
+    Unit(Faction),
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            Tile::Wall => write!(f, "#"),
+            Tile::Empty => write!(f, "."),
+            Tile::Unit(faction) => write!(f, "{}", faction),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct World {
+    tiles: Grid<Tile>,
+    units: Vec<Unit>,
+    rules: Rules,
+}
+
+impl World {
+    /// Changes the rules of the battle and gives every unit the health points of its faction, so
+    /// this should be done before fighting.
+    pub fn with_rules(mut self, rules: Rules) -> World {
+        for unit in self.units.iter_mut() {
+            unit.health_points = rules.stats(unit.faction).health_points;
+        }
+        self.rules = rules;
+        self
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    /// Returns the factions that have units on the map.
+    pub fn factions(&self) -> Vec<Faction> {
+        let mut factions = Vec::new();
+        for unit in self.units.iter().filter(|u| u.is_alive()) {
+            if !factions.contains(&unit.faction) {
+                factions.push(unit.faction);
+            }
+        }
+        factions
+    }
+
+    fn set_tile(&mut self, position: Point, tile: Tile) {
+        self.tiles[position] = tile;
+    }
+
+    fn is_open(&self, position: Point) -> bool {
+        matches!(self.tiles.get(position), Some(Tile::Empty))
+    }
+}
+
+impl World {
+    /// Returns the first step from `from` towards the closest of the targets. If several targets
+    /// are equally close the first in reading order is chosen, and if there are multiple shortest
+    /// paths to it the first step in reading order is taken. `targets` must be sorted.
+    ///
+    /// Searches from all targets at once, which gives the distance to the closest targets and the
+    /// first of them for every square. The step is then the square next to `from` that is closest
+    /// to a target, with the first target and then the first square in reading order breaking
+    /// ties.
+    fn find_move(&self, search: &mut GridBfs, from: Point, targets: &[Point]) -> Option<Point> {
+        if targets.contains(&from) {
+            return None;
+        }
+
+        search.search(targets, |p| self.is_open(p), |p| p.is_adjacent_to(from));
+        from.neighbours()
+            .iter()
+            .filter_map(|&p| Some((search.distance(p)?, search.source(p)?, p)))
+            .min()
+            .map(|(_, _, p)| p)
+    }
+}
+
+impl World {
+    /// Renders the map with the health points of the units on each row next to it, like the
+    /// examples in the puzzle.
+    pub fn render(&self) -> String {
+        let mut units: Vec<&Unit> = self.units.iter().filter(|u| u.is_alive()).collect();
+        units.sort_by_key(|u| u.position);
+
+        let mut output = String::new();
+        for (y, row) in self.tiles.rows().enumerate() {
+            for tile in row {
+                output += &tile.to_string();
+            }
+            let health: Vec<String> = units
+                .iter()
+                .filter(|u| u.position.y == y as i32)
+                .map(|u| format!("{}({})", u.faction, u.health_points))
+                .collect();
+            if !health.is_empty() {
+                output += "   ";
+                output += &health.join(", ");
+            }
+            output += "\n";
+        }
+        output
+    }
+}
+
+impl World {
+    fn result(
+        &self,
+        rounds: i32,
+        winner: Option<Faction>,
+        deaths: HashMap<Faction, usize>,
+    ) -> SimulationResult {
+        let health_points_sum: i32 = self
+            .units
+            .iter()
+            .filter(|u| u.is_alive())
+            .map(|u| u.health_points)
+            .sum();
+        SimulationResult {
+            outcome: health_points_sum * rounds,
+            rounds,
+            winner,
+            deaths,
+        }
+    }
+}
+
+impl Display for World {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self.tiles)
+    }
+}
+
+pub struct SimulationResult {
+    pub outcome: i32,
+    /// The number of full rounds fought.
+    pub rounds: i32,
+    /// The faction that is left, or `None` if the battle ended in a stalemate.
+    pub winner: Option<Faction>,
+    deaths: HashMap<Faction, usize>,
+}
+
+impl SimulationResult {
+    /// Returns how many units of `faction` died.
+    pub fn deaths(&self, faction: Faction) -> usize {
+        self.deaths.get(&faction).copied().unwrap_or(0)
+    }
+}
+
+pub fn run_simulation(mut world: World) -> SimulationResult {
+    fight(&mut world, None, |_, _| {}).unwrap()
+}
+
+/// Like `run_simulation` but gives up and returns `None` as soon as a unit of `faction` dies.
+pub fn run_flawless(world: &World, faction: Faction) -> Option<SimulationResult> {
+    fight(&mut world.clone(), Some(faction), |_, _| {})
+}
+
+/// Fights the battle until one faction is left or a full round passes in which no unit moves or
+/// deals damage, which would repeat forever. Passes every event to `log` together with the world
+/// right after it happened. Returns `None` if a unit of the `protected` faction dies.
+fn fight<F>(world: &mut World, protected: Option<Faction>, mut log: F) -> Option<SimulationResult>
+where
+    F: FnMut(&World, &Event),
+{
+    let mut round = 1;
+    let mut deaths = HashMap::new();
+    let mut search = GridBfs::new(&world.tiles);
+    loop {
+        let mut progress = false;
+
+        // Iterate over all units
+        for i in 0..world.units.len() {
+            let mut current_position = world.units[i].position;
+            let faction = world.units[i].faction;
+
+            // Skip units killed in this round
+            if world.units[i].is_dead() {
+                continue;
+            }
+
+            // Find the other life targets
+            let mut targets = world
+                .units
+                .iter()
+                .filter(|u| {
+                    !std::ptr::eq(*u, &world.units[i]) && u.faction != faction && u.is_alive()
+                })
+                .peekable();
+
+            // See if there are still targets
+            if targets.peek().is_none() {
+                // The battle is over
+                return Some(world.result(round - 1, Some(faction), deaths));
+            }
+
+            // If there are no targets in range
+            if !targets
+                .clone()
+                .any(|target| target.position.is_adjacent_to(current_position))
+            {
+                // Find all adjacent squares
+                let mut target_positions: Vec<Point> = targets
+                    .flat_map(|t| t.position.neighbours().to_vec())
+                    .filter(|pos| world.is_open(*pos))
+                    .collect();
+                target_positions.sort();
+                target_positions.dedup();
+
+                // Find the closest reachable square
+                if let Some(move_square) =
+                    world.find_move(&mut search, world.units[i].position, &target_positions)
+                {
+                    let unit = &mut world.units[i];
+                    let old_position = unit.position;
+                    unit.position = move_square;
+                    let id = unit.id;
+                    world.set_tile(old_position, Tile::Empty);
+                    world.set_tile(move_square, Tile::Unit(faction));
+                    current_position = move_square;
+                    progress = true;
+                    log(
+                        world,
+                        &Event::Move {
+                            unit: id,
+                            from: old_position,
+                            to: move_square,
+                        },
+                    );
+                }
+            }
+
+            // Attack the weakest target in range, the first in reading order if there are several
+            let attack_points = world.rules.stats(faction).attack_power;
+            let target = (0..world.units.len())
+                .filter(|&j| {
+                    let u = &world.units[j];
+                    u.is_alive()
+                        && u.position.is_adjacent_to(current_position)
+                        && u.faction != faction
+                })
+                .min_by_key(|&j| (world.units[j].health_points, world.units[j].position));
+            if let Some(j) = target {
+                let target = &mut world.units[j];
+                target.health_points -= attack_points;
+                progress |= attack_points > 0;
+                let target = target.clone();
+                log(
+                    world,
+                    &Event::Attack {
+                        attacker: world.units[i].id,
+                        target: target.id,
+                        damage: attack_points,
+                        health_points: target.health_points.max(0),
+                    },
+                );
+                if target.is_dead() {
+                    world.set_tile(target.position, Tile::Empty);
+                    *deaths.entry(target.faction).or_insert(0) += 1;
+                    log(
+                        world,
+                        &Event::Death {
+                            unit: target.id,
+                            position: target.position,
+                        },
+                    );
+                    if protected == Some(target.faction) {
+                        return None;
+                    }
+                }
+            }
+        }
+
+        world.units.retain(|u| u.is_alive());
+        world.units.sort_by_key(|u| u.position);
+        log(
+            world,
+            &Event::RoundEnd {
+                round,
+                units: world.units.iter().map(Unit::status).collect(),
+            },
+        );
+
+        if !progress {
+            return Some(world.result(round, None, deaths));
+        }
+        round += 1;
+    }
+}
+
+/// The state of the battle after a number of rounds.
+pub struct Frame {
+    /// The number of full rounds fought.
+    pub round: i32,
+    pub world: World,
+    /// What happened since the previous frame.
+    pub events: Vec<Event>,
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self.round {
+            0 => writeln!(f, "Initially:")?,
+            1 => writeln!(f, "After 1 round:")?,
+            round => writeln!(f, "After {} rounds:", round)?,
+        }
+        write!(f, "{}", self.world.render())?;
+        for event in self.events.iter() {
+            writeln!(f, "{}", event)?;
+        }
+        Ok(())
+    }
+}
+
+/// A recorded battle that can be stepped through round by round.
+pub struct Replay {
+    /// The initial state, the state after every full round and the state at the end of the
+    /// battle if it ended halfway through a round.
+    pub frames: Vec<Frame>,
+    pub result: SimulationResult,
+}
+
+impl Replay {
+    pub fn record(world: &World) -> Replay {
+        let mut frames = vec![Frame {
+            round: 0,
+            world: world.clone(),
+            events: Vec::new(),
+        }];
+        let mut events = Vec::new();
+        let mut world = world.clone();
+        let result = fight(&mut world, None, |world, event| {
+            events.push(event.clone());
+            if let Event::RoundEnd { round, .. } = event {
+                frames.push(Frame {
+                    round: *round,
+                    world: world.clone(),
+                    events: std::mem::take(&mut events),
+                });
+            }
+        });
+        if !events.is_empty() {
+            frames.push(Frame {
+                round: frames.last().unwrap().round,
+                world,
+                events,
+            });
+        }
+        Replay {
+            frames,
+            result: result.unwrap(),
+        }
+    }
+
+    /// Returns all events of the battle in order.
+    pub fn events(&self) -> impl Iterator<Item = &Event> {
+        self.frames.iter().flat_map(|frame| frame.events.iter())
+    }
+}
+
+/// Parses a map in which every capital letter is a unit of that faction. All units get the
+/// health points of `rules`.
+fn parse_map(input: &str, rules: Rules) -> Result<World, ParseError> {
+    let mut units = Vec::new();
+    let tiles = Grid::parse(input, "tile", |point, c| match c {
+        '#' => Some(Tile::Wall),
+        '.' => Some(Tile::Empty),
+        'A'..='Z' => {
+            let faction = Faction(c);
+            let health_points = rules.stats(faction).health_points;
+            units.push(Unit::new(units.len(), faction, health_points, point));
+            Some(Tile::Unit(faction))
+        }
+        _ => None,
+    })?;
+
+    Ok(World {
+        tiles,
+        units,
+        rules,
+    })
+}
+
+pub fn parse(input: &str) -> Result<World, ParseError> {
+    parse_map(input, Rules::default())
+}
+
+/// A battle to try out, with the rules and optionally the expected outcome.
+pub struct Scenario {
+    pub world: World,
+    pub expected_outcome: Option<i32>,
+}
+
+/// Parses a scenario, which is a map preceded by lines that set the stats of factions or the
+/// expected outcome and an empty line that separates them from the map:
+///
+/// ```text
+/// E: 200 hit points, 10 attack power
+/// D: 300 hit points, 5 attack power
+/// outcome: 1234
+///
+/// #######
+/// #E.D.G#
+/// #######
+/// ```
+///
+/// A plain map without any of these lines is a scenario with the default stats.
+pub fn parse_scenario(input: &str) -> Result<Scenario, ParseError> {
+    let stats_regex = Regex::new(r"^([A-Z]): (\d+) hit points, (\d+) attack power$").unwrap();
+    let mut rules = Rules::default();
+    let mut expected_outcome = None;
+
+    // Without an empty line followed by more lines the whole input is the map
+    let lines: Vec<Span> = lines(input).collect();
+    let map_start = match lines.iter().position(|line| line.is_empty()) {
+        Some(end) if lines[end..].iter().any(|line| !line.is_empty()) => end + 1,
+        _ => 0,
+    };
+    let header = &lines[..map_start.saturating_sub(1)];
+    for line in header {
+        if let Ok(outcome) = line.strip_prefix("outcome: ") {
+            expected_outcome = Some(outcome.parse()?);
+        } else {
+            let captures = line.captures(&stats_regex, "F: n hit points, n attack power")?;
+            let faction = Faction(captures.str(1).chars().next().unwrap());
+            let stats = Stats {
+                health_points: captures.parse(2)?,
+                attack_power: captures.parse(3)?,
+            };
+            if stats.health_points == 0 {
+                return Err(captures
+                    .get(2)
+                    .unwrap()
+                    .error("expected positive hit points"));
+            } else if stats.attack_power == 0 {
+                return Err(captures
+                    .get(3)
+                    .unwrap()
+                    .error("expected positive attack power"));
+            }
+            rules.set_stats(faction, stats);
+        }
+    }
+
+    let map: Vec<&str> = lines[map_start..].iter().map(|line| line.text).collect();
+    let world = parse_map(&map.join("\n"), rules).map_err(|mut error| {
+        error.line += map_start;
+        error
+    })?;
+    Ok(Scenario {
+        world,
+        expected_outcome,
+    })
+}
+
+pub fn part1(world: &World) -> i32 {
+    run_simulation(world.clone()).outcome
+}
+
+/// Finds the smallest attack power for the elves that lets them win without a single elf dying.
+/// Returns the attack power and the outcome of the battle, or `None` if there is no such attack
+/// power. Winning does not necessarily get easier with more attack power, so every attack power
+/// is tried, several at once in parallel. Once every hit kills, more attack power cannot change
+/// the battle, so the search stops at the largest health of any enemy.
+pub fn min_flawless_attack_power(world: &World) -> Option<(i32, i32)> {
+    let rules = world.rules();
+    let max_health = world
+        .factions()
+        .into_iter()
+        .filter(|faction| *faction != Faction::ELF)
+        .map(|faction| rules.stats(faction).health_points)
+        .max()
+        .unwrap_or(0);
+    let (attack_power, result) = battle::min_boost_parallel(
+        4..=max_health.max(4),
+        battle::available_threads(),
+        |attack_power| {
+            let rules = rules.clone().with_attack_power(Faction::ELF, attack_power);
+            run_flawless(&world.clone().with_rules(rules), Faction::ELF)
+                .filter(|result| result.winner == Some(Faction::ELF))
+        },
+    )?;
+    Some((attack_power, result.outcome))
+}
+
+/// Returns the outcome of the battle with the smallest attack power for the elves that lets them
+/// win without a single elf dying.
+pub fn part2(world: &World) -> i32 {
+    min_flawless_attack_power(world)
+        .expect("the elves cannot win without losses at any attack power")
+        .1
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &World) -> (i32, i32) {
+    (part1(input), part2(input))
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = World;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn replay() {
+        let world = parse(
+            "#######
+#.G...#
+#...EG#
+#.#.#G#
+#..G#E#
+#.....#
+#######",
+        )
+        .unwrap();
+        let replay = Replay::record(&world);
+        assert_eq!(replay.result.outcome, 27730);
+        assert_eq!(replay.frames.len(), 48);
+        assert_eq!(
+            replay.frames[1].world.render(),
+            "#######
+#..G..#   G(200)
+#...EG#   E(197), G(197)
+#.#G#G#   G(200), G(197)
+#...#E#   E(197)
+#.....#
+#######
+"
+        );
+        assert_eq!(
+            replay.frames[47].world.render(),
+            "#######
+#G....#   G(200)
+#.G...#   G(131)
+#.#.#G#   G(59)
+#...#.#
+#....G#   G(200)
+#######
+"
+        );
+        let deaths = replay
+            .events()
+            .filter(|e| matches!(e, Event::Death { .. }))
+            .count();
+        assert_eq!(deaths, 2);
+
+        let with_attack_power = |attack_power| {
+            let rules = Rules::default().with_attack_power(Faction::ELF, attack_power);
+            world.clone().with_rules(rules)
+        };
+        assert!(run_flawless(&with_attack_power(14), Faction::ELF).is_none());
+        let result = run_flawless(&with_attack_power(15), Faction::ELF).unwrap();
+        assert_eq!((result.outcome, result.winner), (4988, Some(Faction::ELF)));
+        assert_eq!(min_flawless_attack_power(&world), Some((15, 4988)));
+    }
+
+    /// The other examples of the puzzle with the outcomes of both parts.
+    const EXAMPLES: [(&str, i32, Option<i32>); 5] = [
+        (
+            "#######
+#G..#E#
+#E#E.E#
+#G.##.#
+#...#E#
+#...E.#
+#######",
+            36334,
+            None,
+        ),
+        (
+            "#######
+#E..EG#
+#.#G.E#
+#E.##E#
+#G..#.#
+#..E#.#
+#######",
+            39514,
+            Some(31284),
+        ),
+        (
+            "#######
+#E.G#.#
+#.#G..#
+#G.#.G#
+#G..#.#
+#...E.#
+#######",
+            27755,
+            Some(3478),
+        ),
+        (
+            "#######
+#.E...#
+#.#..G#
+#.###.#
+#E#G#G#
+#...#G#
+#######",
+            28944,
+            Some(6474),
+        ),
+        (
+            "#########
+#G......#
+#.E.#...#
+#..##..G#
+#...##..#
+#...#...#
+#.G...G.#
+#.....G.#
+#########",
+            18740,
+            Some(1140),
+        ),
+    ];
+
+    /// Checks what must hold for any battle, whatever the rules.
+    fn check_invariants(world: &World, result: &SimulationResult) {
+        let replay = Replay::record(world);
+        assert_eq!(replay.result.outcome, result.outcome);
+        let survivors = &replay.frames.last().unwrap().world.units;
+        let survivors: Vec<&Unit> = survivors.iter().filter(|u| u.is_alive()).collect();
+        assert!(survivors.iter().all(|u| Some(u.faction) == result.winner));
+        let health_points: i32 = survivors.iter().map(|u| u.health_points).sum();
+        assert_eq!(result.outcome, result.rounds * health_points);
+        let deaths: usize = world.factions().iter().map(|f| result.deaths(*f)).sum();
+        assert_eq!(deaths + survivors.len(), world.units.len());
+    }
+
+    #[test]
+    fn scenarios() {
+        for &(map, outcome, flawless_outcome) in EXAMPLES.iter() {
+            let scenario = parse_scenario(&format!("outcome: {}\n\n{}", outcome, map)).unwrap();
+            let result = run_simulation(scenario.world.clone());
+            assert_eq!(Some(result.outcome), scenario.expected_outcome);
+            check_invariants(&scenario.world, &result);
+            if let Some(flawless_outcome) = flawless_outcome {
+                assert_eq!(part2(&scenario.world), flawless_outcome);
+            }
+        }
+
+        // A third faction that fights both the elves and the goblins
+        let scenario = parse_scenario(
+            "E: 100 hit points, 10 attack power
+D: 300 hit points, 5 attack power
+
+#########
+#E..D..G#
+#.......#
+#########",
+        )
+        .unwrap();
+        let world = &scenario.world;
+        assert_eq!(
+            world.factions(),
+            vec![Faction::ELF, Faction('D'), Faction::GOBLIN]
+        );
+        assert_eq!(
+            world.render().lines().nth(1),
+            Some("#E..D..G#   E(100), D(300), G(200)")
+        );
+        let result = run_simulation(world.clone());
+        check_invariants(world, &result);
+        assert_eq!((result.winner, result.rounds), (Some(Faction::GOBLIN), 39));
+        assert_eq!(result.deaths(Faction::ELF), 1);
+        assert_eq!(result.deaths(Faction('D')), 1);
+
+        // Without walls around the map the units must not step off it
+        let world = parse(".....\nE...G").unwrap();
+        let result = run_simulation(world.clone());
+        assert_eq!(
+            (result.winner, result.rounds, result.outcome),
+            (Some(Faction::ELF), 68, 136)
+        );
+        assert_eq!(part2(&world), 2703);
+
+        // Factions that can't reach each other end in a stalemate, whatever the attack power
+        let world = parse("#E#G#").unwrap();
+        let result = run_simulation(world.clone());
+        assert_eq!(
+            (result.winner, result.rounds, result.outcome),
+            (None, 1, 400)
+        );
+        assert_eq!(min_flawless_attack_power(&world), None);
+        let world = parse("#E.G#").unwrap();
+        let rules = Rules::default().with_attack_power(Faction::ELF, 0);
+        let replay = Replay::record(&world.with_rules(rules));
+        assert_eq!(replay.result.winner, Some(Faction::GOBLIN));
+        let world = parse("#EG#").unwrap();
+        let rules = Rules::default()
+            .with_attack_power(Faction::ELF, 0)
+            .with_attack_power(Faction::GOBLIN, 0);
+        let replay = Replay::record(&world.with_rules(rules));
+        assert_eq!(replay.result.winner, None);
+        assert_eq!(replay.frames.len(), 2);
+
+        let error = parse_scenario("E: 3 hit points, 0 attack power\n\n#E#")
+            .err()
+            .unwrap();
+        assert_eq!((error.line, error.column), (1, Some(18)));
+        let error = parse_scenario("E: 0 hit points, 3 attack power\n\n#E#")
+            .err()
+            .unwrap();
+        assert_eq!((error.line, error.column), (1, Some(4)));
+        let error = parse_scenario("outcome: 3\n\n###\n#E?").err().unwrap();
+        assert_eq!((error.line, error.column), (4, Some(3)));
+        let error = parse_scenario("outcome: 3\n###\n\n#E#").err().unwrap();
+        assert_eq!((error.line, error.column), (2, Some(1)));
+
+        // The map only needs to start with a wall if there is no header
+        let scenario =
+            parse_scenario("E: 100 hit points, 10 attack power\n\n.....\nE...G").unwrap();
+        assert_eq!(
+            scenario.world.rules().stats(Faction::ELF).health_points,
+            100
+        );
+        assert!(parse_scenario(".....\nE...G\n\n").is_ok());
+    }
+}
@@ -0,0 +1,208 @@
+use crate::parse::{end_of_input, lines, Captures};
+use crate::vm::{self, Machine, Opcode, Program, Value};
+use crate::{ParseError, Solution};
+use regex::Regex;
+use std::iter::Peekable;
+
+#[derive(Debug, Copy, Clone)]
+pub struct OpcodeArgs(pub Value, pub Value, pub Value);
+
+pub type Registers = [Value; 4];
+
+#[derive(Debug, Clone)]
+pub struct Sample {
+    pub before: Registers,
+    pub opcode: u8,
+    pub opcode_args: OpcodeArgs,
+    pub after: Registers,
+}
+
+fn parse_registers(captures: &Captures) -> Result<Registers, ParseError> {
+    Ok([
+        captures.parse(1)?,
+        captures.parse(2)?,
+        captures.parse(3)?,
+        captures.parse(4)?,
+    ])
+}
+
+fn parse_opcode(captures: &Captures) -> Result<u8, ParseError> {
+    let opcode = captures.parse(1)?;
+    if opcode < 16 {
+        Ok(opcode)
+    } else {
+        Err(captures.get(1).unwrap().error("opcodes range from 0 to 15"))
+    }
+}
+
+/// Parses the operands of an instruction. Operand c is always a register. If `registers_only` is
+/// set, a and b must be valid registers as well.
+fn parse_args(captures: &Captures, registers_only: bool) -> Result<OpcodeArgs, ParseError> {
+    let first = if registers_only { 2 } else { 4 };
+    for group in first..=4 {
+        if captures.parse::<Value>(group)? >= 4 {
+            return Err(captures
+                .get(group)
+                .unwrap()
+                .error("registers range from 0 to 3"));
+        }
+    }
+    Ok(OpcodeArgs(
+        captures.parse(2)?,
+        captures.parse(3)?,
+        captures.parse(4)?,
+    ))
+}
+
+impl Sample {
+    /// Returns whether the sample is consistent with the instruction having the given opcode.
+    pub fn behaves_like(&self, opcode: Opcode) -> bool {
+        let OpcodeArgs(a, b, c) = self.opcode_args;
+        let mut registers = self.before;
+        vm::Instruction::new(opcode, a, b, c)
+            .execute(&mut registers)
+            .is_ok()
+            && registers == self.after
+    }
+}
+
+/// An instruction in the test program with its unresolved opcode number.
+pub struct Instruction(pub u8, pub OpcodeArgs);
+
+impl Instruction {
+    /// Resolves the opcode number using the mapping found by `resolve_opcodes`.
+    pub fn decode(&self, opcode_mapping: &[Opcode; 16]) -> vm::Instruction {
+        let OpcodeArgs(a, b, c) = self.1;
+        vm::Instruction::new(opcode_mapping[self.0 as usize], a, b, c)
+    }
+}
+
+/// Figures out the opcode mappings from the samples by power of elimination.
+pub fn resolve_opcodes(samples: &[Sample]) -> [Opcode; 16] {
+    let mut opcode_possibilities: [[bool; 16]; 16] = [[true; 16]; 16];
+    for sample in samples.iter() {
+        for opcode in Opcode::values() {
+            if !sample.behaves_like(opcode) {
+                opcode_possibilities[sample.opcode as usize][opcode as usize] = false;
+            }
+        }
+    }
+
+    let mut opcode_mapping: [Opcode; 16] = [Opcode::Addi; 16];
+    for _ in 0..16 {
+        for i in 0..16 {
+            let options: Vec<usize> = opcode_possibilities[i]
+                .iter()
+                .enumerate()
+                .filter(|(_, possible)| **possible)
+                .map(|(i, _)| i)
+                .collect();
+            if options.len() == 1 {
+                let opcode = Opcode::ALL[options[0]];
+                opcode_mapping[i] = opcode;
+                for possibilities in opcode_possibilities.iter_mut() {
+                    possibilities[options[0]] = false;
+                }
+            }
+        }
+    }
+    opcode_mapping
+}
+
+pub fn parse(input: &str) -> Result<(Vec<Sample>, Vec<Instruction>), ParseError> {
+    let before_regex = Regex::new(r"^Before: \[(\d+), (\d+), (\d+), (\d+)\]$").unwrap();
+    let after_regex = Regex::new(r"^After:  \[(\d+), (\d+), (\d+), (\d+)\]$").unwrap();
+    let instruction_regex = Regex::new(r"^(\d+) (\d+) (\d+) (\d+)$").unwrap();
+    let end = end_of_input(input);
+
+    let mut input_iter = lines(input).peekable();
+    let next_line = |input_iter: &mut Peekable<_>, expected: &str| {
+        input_iter
+            .next()
+            .ok_or_else(|| ParseError::new(end, None, format!("expected `{}`", expected)))
+    };
+
+    // Parse the samples, which each start with a "Before:" line
+    let mut samples = Vec::new();
+    while let Some(before_line) = input_iter.next_if(|l| l.text.starts_with("Before:")) {
+        let before = before_line.captures(&before_regex, "Before: [a, b, c, d]")?;
+        let instruction = next_line(&mut input_iter, "opcode a b c")?
+            .captures(&instruction_regex, "opcode a b c")?;
+        let after = next_line(&mut input_iter, "After:  [a, b, c, d]")?
+            .captures(&after_regex, "After:  [a, b, c, d]")?;
+        samples.push(Sample {
+            before: parse_registers(&before)?,
+            opcode: parse_opcode(&instruction)?,
+            opcode_args: parse_args(&instruction, false)?,
+            after: parse_registers(&after)?,
+        });
+        input_iter.next_if(|l| l.is_empty());
+    }
+
+    // Parse sample program. Which operands are registers depends on the opcodes, which are only
+    // known once the samples are resolved, so any operand could be a register.
+    let program = input_iter
+        .skip_while(|l| l.is_empty())
+        .map(|l| {
+            let instruction = l.captures(&instruction_regex, "opcode a b c")?;
+            Ok(Instruction(
+                parse_opcode(&instruction)?,
+                parse_args(&instruction, true)?,
+            ))
+        })
+        .collect::<Result<_, ParseError>>()?;
+
+    Ok((samples, program))
+}
+
+pub fn part1((samples, _): &(Vec<Sample>, Vec<Instruction>)) -> usize {
+    samples
+        .iter()
+        .filter(|sample| {
+            Opcode::values()
+                .filter(|opcode| sample.behaves_like(*opcode))
+                .count()
+                >= 3
+        })
+        .count()
+}
+
+pub fn part2((samples, program): &(Vec<Sample>, Vec<Instruction>)) -> Value {
+    let opcode_mapping = resolve_opcodes(samples);
+    let program = Program {
+        ip_register: None,
+        instructions: program
+            .iter()
+            .map(|instruction| instruction.decode(&opcode_mapping))
+            .collect(),
+    };
+
+    let mut machine = Machine::new(&program, 4);
+    machine.run(None).expect("all operands are valid registers");
+    machine.registers[0]
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &(Vec<Sample>, Vec<Instruction>)) -> (usize, Value) {
+    (part1(input), part2(input))
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = (Vec<Sample>, Vec<Instruction>);
+    type Part1 = usize;
+    type Part2 = Value;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
@@ -0,0 +1,298 @@
+use crate::parse::lines;
+use crate::{Bounds, Grid, ParseError, Point, Solution};
+use std::fmt::Display;
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+enum Ground {
+    Clay,
+    Sand,
+    Dried,
+    Water,
+    Source,
+}
+
+#[derive(Debug)]
+pub struct Vein {
+    pub from: Point,
+    pub to: Point,
+}
+
+#[derive(Clone)]
+pub struct World {
+    tiles: Grid<Ground>,
+    /// The smallest y coordinate of any clay, rows above it are not counted.
+    min_y: i32,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum Flow {
+    Down,
+    Blocked,
+}
+
+impl World {
+    pub fn new(veins: &[Vein]) -> World {
+        let clay = Bounds::from_points(veins.iter().flat_map(|vein| vec![vein.from, vein.to]))
+            .expect("no veins of clay");
+
+        // Leave a column of sand on both sides so water can flow down past the outermost clay,
+        // and include the spring at the top, even if it is not above any clay.
+        let bounds = Bounds {
+            min: Point::new((clay.min.x - 1).min(500), 0),
+            max: Point::new((clay.max.x + 1).max(500), clay.max.y),
+        };
+        let mut world = World {
+            tiles: Grid::from_bounds(bounds, Ground::Sand),
+            min_y: clay.min.y,
+        };
+        world.set_tile(Point::new(500, 0), Ground::Source);
+        for vein in veins {
+            for y in vein.from.y..=vein.to.y {
+                for x in vein.from.x..=vein.to.x {
+                    world.set_tile(Point::new(x, y), Ground::Clay);
+                }
+            }
+        }
+        world
+    }
+
+    fn set_tile(&mut self, position: Point, tile: Ground) {
+        let current = &mut self.tiles[position];
+        if tile != Ground::Clay {
+            assert_ne!(*current, Ground::Clay);
+        }
+        *current = tile;
+    }
+
+    fn blocked(&self, position: Point) -> bool {
+        self.tiles.get(position).is_some_and(|t| match t {
+            Ground::Clay => true,
+            Ground::Sand => false,
+            Ground::Dried => false,
+            Ground::Water => true,
+            Ground::Source => false,
+        })
+    }
+
+    pub fn flow(&mut self, Point { x, y }: Point) {
+        let height = self.tiles.height() as i32;
+
+        // Move all the way down until we hit clay
+        let bottom = {
+            let mut y = y;
+            while y < height && !self.blocked(Point::new(x, y + 1)) {
+                self.set_tile(Point::new(x, y), Ground::Dried);
+                y += 1;
+            }
+            Point::new(x, y)
+        };
+
+        // If there is already water here we can be sure we've reached this place already.
+        if let Some(Ground::Dried) = self.tiles.get(bottom) {
+            return;
+        }
+
+        // Keep filling up rows until we no longer can
+        let mut bottom_row = bottom.y;
+        while bottom_row >= y && bottom_row < height {
+            let (left, left_flow) = self.find_flow(Point::new(x, bottom_row), -1);
+            let (right, right_flow) = self.find_flow(Point::new(x, bottom_row), 1);
+
+            if left_flow == Flow::Blocked && right_flow == Flow::Blocked {
+                for x in left..=right {
+                    self.set_tile(Point::new(x, bottom_row), Ground::Water);
+                }
+                bottom_row -= 1;
+            } else {
+                for x in left..=right {
+                    self.set_tile(Point::new(x, bottom_row), Ground::Dried);
+                }
+                if left_flow == Flow::Down && left >= self.tiles.origin().x {
+                    self.flow(Point::new(left, bottom_row + 1));
+                }
+                if right_flow == Flow::Down && right <= self.tiles.max().x {
+                    self.flow(Point::new(right, bottom_row + 1));
+                }
+
+                // Check if by flowing over edges we filled an entire container and we have to reevaluate this row.
+                let (new_left, new_left_flow) = self.find_flow(Point::new(x, bottom_row), -1);
+                let (new_right, new_right_flow) = self.find_flow(Point::new(x, bottom_row), 1);
+                if new_left == left
+                    && new_right == right
+                    && new_left_flow == left_flow
+                    && new_right_flow == right_flow
+                {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn find_flow(&self, Point { x, y }: Point, direction: i32) -> (i32, Flow) {
+        let mut pos = x;
+        loop {
+            if !self.blocked(Point::new(pos, y + 1)) {
+                return (pos, Flow::Down);
+            }
+
+            if self.blocked(Point::new(pos + direction, y)) {
+                return (pos, Flow::Blocked);
+            }
+
+            pos += direction;
+        }
+    }
+
+    /// Counts the tiles below the highest clay for which `f` returns true.
+    fn count(&self, f: impl Fn(&Ground) -> bool) -> usize {
+        self.tiles
+            .iter()
+            .filter(|(position, ground)| position.y >= self.min_y && f(ground))
+            .count()
+    }
+
+    pub fn count_water_and_dried(&self) -> usize {
+        self.count(|ground| *ground == Ground::Dried || *ground == Ground::Water)
+    }
+
+    pub fn count_water(&self) -> usize {
+        self.count(|ground| *ground == Ground::Water)
+    }
+}
+
+impl Display for Ground {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "{}",
+            match self {
+                Ground::Sand => ".",
+                Ground::Clay => "#",
+                Ground::Dried => "|",
+                Ground::Water => "~",
+                Ground::Source => "+",
+            }
+        )
+    }
+}
+
+impl Display for World {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self.tiles)
+    }
+}
+
+/// Lets the water flow from the spring and returns the resulting world.
+pub fn flow(veins: &[Vein]) -> World {
+    let mut world = World::new(veins);
+    world.flow(Point::new(500, 1));
+    world
+}
+
+pub fn parse(input: &str) -> Result<Vec<Vein>, ParseError> {
+    let regex = regex::Regex::new(r"^([xy])=(\d+), ([xy])=(\d+)\.\.(\d+)$").unwrap();
+    let veins: Vec<Vein> = lines(input)
+        .map(|line| {
+            let captures = line.captures(&regex, "x=a, y=b..c")?;
+            if captures.str(1) == captures.str(3) {
+                return Err(captures.get(3).unwrap().error("expected a different axis"));
+            }
+            let (from, to) = (captures.parse(4)?, captures.parse(5)?);
+            if from > to {
+                return Err(captures.get(4).unwrap().error("range is reversed"));
+            }
+            let vein = if captures.str(1) == "x" {
+                let x = captures.parse(2)?;
+                Vein {
+                    from: Point::new(x, from),
+                    to: Point::new(x, to),
+                }
+            } else {
+                let y = captures.parse(2)?;
+                Vein {
+                    from: Point::new(from, y),
+                    to: Point::new(to, y),
+                }
+            };
+            // The water starts flowing right below the spring at x=500, y=0
+            if (vein.from.x..=vein.to.x).contains(&500) && vein.from.y <= 1 {
+                return Err(line.error("clay blocks the spring"));
+            }
+            Ok(vein)
+        })
+        .collect::<Result<_, ParseError>>()?;
+
+    if veins.is_empty() {
+        return Err(ParseError::new(
+            1,
+            None,
+            "expected at least one vein of clay",
+        ));
+    }
+    Ok(veins)
+}
+
+pub fn part1(veins: &[Vein]) -> usize {
+    flow(veins).count_water_and_dried()
+}
+
+pub fn part2(veins: &[Vein]) -> usize {
+    flow(veins).count_water()
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[Vein]) -> (usize, usize) {
+    (part1(input), part2(input))
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Vec<Vein>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let veins = parse(
+            "x=495, y=2..7
+y=7, x=495..501
+x=501, y=3..7
+x=498, y=2..4
+x=506, y=1..2
+x=498, y=10..13
+x=504, y=10..13
+y=13, x=498..504",
+        )
+        .unwrap();
+        assert_eq!(part1(&veins), 57);
+        assert_eq!(part2(&veins), 29);
+    }
+
+    #[test]
+    fn spring_outside_clay() {
+        // The water falls past all clay
+        let veins = parse("x=10, y=1..2").unwrap();
+        assert_eq!((part1(&veins), part2(&veins)), (2, 0));
+
+        let error = parse("x=10, y=1..2\ny=1, x=499..501").err().unwrap();
+        assert_eq!((error.line, error.column), (2, Some(1)));
+    }
+}
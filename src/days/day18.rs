@@ -0,0 +1,225 @@
+use crate::{cycle, Grid, ParseError, Point, Solution};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[repr(u8)]
+pub enum GroundType {
+    Open,
+    Trees,
+    Lumberyard,
+}
+
+/// The number of adjacent acres it takes for an acre to change.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Rules {
+    /// An open acre fills with trees if at least this many adjacent acres contain trees.
+    pub trees_to_grow: usize,
+    /// An acre with trees becomes a lumberyard if at least this many adjacent acres are
+    /// lumberyards.
+    pub lumberyards_to_build: usize,
+    /// A lumberyard remains if at least this many adjacent acres are lumberyards and at least
+    /// `trees_to_remain` contain trees, otherwise it becomes open.
+    pub lumberyards_to_remain: usize,
+    /// See `lumberyards_to_remain`.
+    pub trees_to_remain: usize,
+}
+
+impl Default for Rules {
+    /// The rules from the puzzle.
+    fn default() -> Rules {
+        Rules {
+            trees_to_grow: 3,
+            lumberyards_to_build: 3,
+            lumberyards_to_remain: 1,
+            trees_to_remain: 1,
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct World {
+    area: Grid<GroundType>,
+    rules: Rules,
+}
+
+impl World {
+    /// Creates a world of any size that evolves by the rules from the puzzle.
+    pub fn new(area: Grid<GroundType>) -> World {
+        World {
+            area,
+            rules: Rules::default(),
+        }
+    }
+
+    /// Returns the world evolving by different rules.
+    pub fn with_rules(self, rules: Rules) -> World {
+        World { rules, ..self }
+    }
+
+    pub fn area(&self) -> &Grid<GroundType> {
+        &self.area
+    }
+
+    /// Counts the acres of the given type adjacent to `position`.
+    fn count_adjacent(&self, position: Point, ground: GroundType) -> usize {
+        self.area
+            .neighbours8(position)
+            .filter(|p| self.area[*p] == ground)
+            .count()
+    }
+
+    /// Returns whether at least `count` acres of the given type are adjacent to `position`.
+    fn has_adjacent(&self, position: Point, ground: GroundType, count: usize) -> bool {
+        // Stopping early is noticeably faster than counting all neighbours
+        count == 0
+            || self
+                .area
+                .neighbours8(position)
+                .filter(|p| self.area[*p] == ground)
+                .nth(count - 1)
+                .is_some()
+    }
+
+    pub fn evolve(&self) -> World {
+        let rules = self.rules;
+        let area = Grid::from_fn(
+            self.area.width(),
+            self.area.height(),
+            |position| match self.area[position] {
+                GroundType::Open => {
+                    if self.count_adjacent(position, GroundType::Trees) >= rules.trees_to_grow {
+                        GroundType::Trees
+                    } else {
+                        GroundType::Open
+                    }
+                }
+                GroundType::Trees => {
+                    if self.count_adjacent(position, GroundType::Lumberyard)
+                        >= rules.lumberyards_to_build
+                    {
+                        GroundType::Lumberyard
+                    } else {
+                        GroundType::Trees
+                    }
+                }
+                GroundType::Lumberyard => {
+                    let next_to_lumberyards = self.has_adjacent(
+                        position,
+                        GroundType::Lumberyard,
+                        rules.lumberyards_to_remain,
+                    );
+                    let next_to_trees =
+                        self.has_adjacent(position, GroundType::Trees, rules.trees_to_remain);
+                    if next_to_lumberyards && next_to_trees {
+                        GroundType::Lumberyard
+                    } else {
+                        GroundType::Open
+                    }
+                }
+            },
+        );
+        World { area, rules }
+    }
+
+    pub fn num_lumberyards(&self) -> usize {
+        self.area
+            .values()
+            .filter(|c| **c == GroundType::Lumberyard)
+            .count()
+    }
+
+    pub fn num_wood(&self) -> usize {
+        self.area
+            .values()
+            .filter(|c| **c == GroundType::Trees)
+            .count()
+    }
+}
+
+pub fn parse(input: &str) -> Result<World, ParseError> {
+    let area = Grid::parse(input, "acre", |_, c| match c {
+        '.' => Some(GroundType::Open),
+        '|' => Some(GroundType::Trees),
+        '#' => Some(GroundType::Lumberyard),
+        _ => None,
+    })?;
+    Ok(World::new(area))
+}
+
+pub fn part1(world: &World) -> usize {
+    let mut world = world.clone();
+    for _ in 0..10 {
+        world = world.evolve();
+    }
+
+    world.num_lumberyards() * world.num_wood()
+}
+
+pub fn part2(world: &World) -> usize {
+    let world = cycle::nth(world.clone(), World::evolve, 1_000_000_000);
+    world.num_lumberyards() * world.num_wood()
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &World) -> (usize, usize) {
+    (part1(input), part2(input))
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = World;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let world = parse(
+            ".#.#...|#.
+.....#|##|
+.|..|...#.
+..|#.....#
+#.#|||#|#|
+...#.||...
+.|....|...
+||...#|.#|
+|.||||..|.
+...#.|..|.",
+        )
+        .unwrap();
+        assert_eq!(part1(&world), 1147);
+    }
+
+    #[test]
+    fn other_sizes_and_rules() {
+        let world = parse("|.|\n#..").unwrap();
+        assert_eq!((world.area().width(), world.area().height()), (3, 2));
+
+        // With a single tree enough for trees to grow every open acre is filled
+        let rules = Rules {
+            trees_to_grow: 1,
+            ..Rules::default()
+        };
+        let world = world.with_rules(rules).evolve();
+        assert_eq!(world.num_wood(), 5);
+        assert_eq!(world.num_lumberyards(), 0);
+
+        assert!(parse("|.|\n#.").is_err());
+    }
+}
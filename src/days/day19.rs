@@ -0,0 +1,131 @@
+use crate::vm::{Machine, Opcode, Program, Value};
+use crate::{ParseError, Solution};
+
+const NUM_REGISTERS: usize = 6;
+
+/// Runs the program to completion with register 0 set to `r0` and returns register 0.
+pub fn run(program: &Program, r0: Value) -> Value {
+    let mut machine = Machine::new(program, NUM_REGISTERS);
+    machine.registers[0] = r0;
+    machine
+        .run(None)
+        .expect("the program only uses valid registers");
+    machine.registers[0]
+}
+
+/// Returns the sum of all divisors of `n`, including 1 and `n` itself.
+pub fn sum_of_divisors(n: Value) -> Value {
+    let mut sum = 0;
+    let mut divisor = 1;
+    while divisor * divisor <= n {
+        if n % divisor == 0 {
+            sum += divisor;
+            if divisor * divisor != n {
+                sum += n / divisor;
+            }
+        }
+        divisor += 1;
+    }
+    sum
+}
+
+/// Looks for the loop that tests whether the product of two counters equals a target register,
+/// which is how the program sums the divisors of a number. Returns the address of the `mulr` and
+/// the register holding the number.
+fn find_divisor_test(program: &Program) -> Option<(usize, Value)> {
+    program
+        .instructions
+        .windows(2)
+        .enumerate()
+        .find_map(|(address, pair)| {
+            let (product, test) = (pair[0], pair[1]);
+            if product.opcode != Opcode::Mulr || test.opcode != Opcode::Eqrr {
+                return None;
+            }
+            if test.a == product.c {
+                Some((address, test.b))
+            } else if test.b == product.c {
+                Some((address, test.a))
+            } else {
+                None
+            }
+        })
+}
+
+/// Computes what the program leaves in register 0 without simulating the divisor sum loop: the
+/// program is only run until it first reaches the loop, at which point the number whose divisors
+/// are summed has been set up. Falls back to `run` if the loop can't be found.
+pub fn run_fast(program: &Program, r0: Value) -> Value {
+    let (address, target) = match find_divisor_test(program) {
+        Some(test) => test,
+        None => return run(program, r0),
+    };
+
+    let mut machine = Machine::new(program, NUM_REGISTERS);
+    machine.registers[0] = r0;
+    machine
+        .run_until(None, |machine| machine.ip == address as Value)
+        .expect("the program only uses valid registers");
+    if machine.is_halted() {
+        return machine.registers[0];
+    }
+    sum_of_divisors(machine.registers[target as usize])
+}
+
+pub fn parse(input: &str) -> Result<Program, ParseError> {
+    Program::parse_with_registers(input, NUM_REGISTERS)
+}
+
+pub fn part1(program: &Program) -> Value {
+    run_fast(program, 0)
+}
+
+pub fn part2(program: &Program) -> Value {
+    run_fast(program, 1)
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &Program) -> (Value, Value) {
+    (part1(input), part2(input))
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Program;
+    type Part1 = Value;
+    type Part2 = Value;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let program = parse(
+            "#ip 0\nseti 5 0 1\nseti 6 0 2\naddi 0 1 0\naddr 1 2 3\nsetr 1 0 0\nseti 8 0 4\nseti 9 0 5",
+        )
+        .unwrap();
+        assert_eq!(run_fast(&program, 0), 6);
+        assert_eq!(sum_of_divisors(12), 28);
+    }
+
+    #[test]
+    fn shortcut_matches_simulation() {
+        let program = parse(include_str!("../../inputs/day19/input")).unwrap();
+        assert_eq!(run_fast(&program, 0), run(&program, 0));
+    }
+}
@@ -0,0 +1,87 @@
+use crate::parse::lines;
+use crate::{ParseError, Solution};
+
+pub fn parse(input: &str) -> Result<Vec<Vec<u8>>, ParseError> {
+    let ids: Vec<Vec<u8>> = lines(input)
+        .map(
+            |line| match line.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+                Some((offset, c)) => Err(line.error_at(
+                    offset,
+                    format!("expected a lowercase letter, found `{}`", c),
+                )),
+                None => Ok(line.text.as_bytes().to_vec()),
+            },
+        )
+        .collect::<Result<_, _>>()?;
+    if ids.is_empty() {
+        return Err(ParseError::new(1, None, "expected at least one box ID"));
+    }
+    Ok(ids)
+}
+
+pub fn part1(lines: &[Vec<u8>]) -> usize {
+    // Walk over all lines and construct an array that contains for every char from a..z
+    // how many times it occurred.
+    let counts: Vec<[usize; 26]> = lines
+        .iter()
+        .map(|line| {
+            let mut counts = [0; 26];
+            for c in line.iter() {
+                counts[(c - 97) as usize] += 1;
+            }
+            counts
+        })
+        .collect();
+
+    // Count the number of times at least one element appeared twice
+    let double_count = counts.iter().filter(|counts| counts.contains(&2)).count();
+
+    // Count the number of times at least one element appeared three times
+    let triple_count = counts.iter().filter(|counts| counts.contains(&3)).count();
+
+    double_count * triple_count
+}
+
+pub fn part2(lines: &[Vec<u8>]) -> String {
+    // Match all ids with all other ids
+    for (idx, a) in lines.iter().enumerate() {
+        for b in lines[idx + 1..].iter() {
+            // Find the dissimilar characters
+            if a.iter().zip(b.iter()).filter(|(a, b)| a != b).count() == 1 {
+                // Found the matching box ID's, now find the characters that do match
+                let similar: Vec<u8> = a
+                    .iter()
+                    .zip(b.iter())
+                    .filter_map(|(a, b)| if a == b { Some(*a) } else { None })
+                    .collect();
+                return String::from_utf8(similar).unwrap();
+            };
+        }
+    }
+    panic!("No matching box IDs found");
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[Vec<u8>]) -> (usize, String) {
+    (part1(input), part2(input))
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Vec<u8>>;
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
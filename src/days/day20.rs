@@ -0,0 +1,211 @@
+use crate::parse::lines;
+use crate::search::{self, Paths};
+use crate::{Bounds, Direction, Grid, ParseError, Point, Solution};
+use std::collections::HashSet;
+use std::fmt::Display;
+
+/// The rooms of the facility and the doors between them. The room we start in is at the origin.
+#[derive(Debug, Clone, Default)]
+pub struct Map {
+    /// Every door as the pair of rooms it connects, with the smaller room first.
+    doors: HashSet<(Point, Point)>,
+}
+
+/// Returns the key of the door between two rooms in `Map::doors`.
+fn door(a: Point, b: Point) -> (Point, Point) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+impl Map {
+    fn add_door(&mut self, a: Point, b: Point) {
+        self.doors.insert(door(a, b));
+    }
+
+    pub fn has_door(&self, a: Point, b: Point) -> bool {
+        self.doors.contains(&door(a, b))
+    }
+
+    /// Returns the rooms that can be reached through a door from `room`.
+    pub fn neighbours(&self, room: Point) -> impl Iterator<Item = Point> + '_ {
+        room.neighbours()
+            .to_vec()
+            .into_iter()
+            .filter(move |n| self.has_door(room, *n))
+    }
+
+    /// Returns the shortest paths from the starting room to every room.
+    pub fn distances(&self) -> Paths<Point> {
+        search::bfs(Point::default(), |room| self.neighbours(room))
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        self.doors
+            .iter()
+            .fold(Bounds::new(Point::default()), |bounds, (a, b)| {
+                bounds.extend(*a).extend(*b)
+            })
+    }
+}
+
+/// Draws the map the way the puzzle does: rooms are `.`, doors are `|` or `-`, everything else
+/// is wall and the starting room is marked with `X`.
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        let bounds = self.bounds();
+        let mut grid = Grid::new(bounds.width() * 2 + 1, bounds.height() * 2 + 1, '#');
+        let cell = |room: Point| (room - bounds.min) * 2 + Point::new(1, 1);
+        for (a, b) in self.doors.iter() {
+            grid[cell(*a)] = '.';
+            grid[cell(*b)] = '.';
+            let door = if a.y == b.y { '|' } else { '-' };
+            grid[cell(*a) + (*b - *a)] = door;
+        }
+        grid[cell(Point::default())] = 'X';
+        write!(f, "{}", grid)
+    }
+}
+
+/// Follows every route described by the regex and records the doors passed through.
+pub fn parse(input: &str) -> Result<Map, ParseError> {
+    let line = lines(input)
+        .next()
+        .ok_or_else(|| ParseError::new(1, None, "expected a regex"))?
+        .trim();
+    let regex = line.strip_prefix("^")?.strip_suffix("$")?;
+
+    let mut map = Map::default();
+    // The rooms the routes followed so far can end in
+    let mut current = vec![Point::default()];
+    // For every open group the offset of its `(`, the rooms the group started in and the rooms
+    // the alternatives finished so far end in
+    let mut groups: Vec<(usize, Vec<Point>, Vec<Point>)> = Vec::new();
+    for (offset, c) in regex.char_indices() {
+        match c {
+            'N' | 'E' | 'S' | 'W' => {
+                let direction = match c {
+                    'N' => Direction::Up,
+                    'E' => Direction::Right,
+                    'S' => Direction::Down,
+                    _ => Direction::Left,
+                };
+                for room in current.iter_mut() {
+                    let next = room.step(direction);
+                    map.add_door(*room, next);
+                    *room = next;
+                }
+            }
+            '(' => groups.push((offset, current.clone(), Vec::new())),
+            '|' => match groups.last_mut() {
+                Some((_, start, ends)) => {
+                    ends.append(&mut current);
+                    current = start.clone();
+                }
+                None => return Err(regex.error_at(offset, "`|` outside of a group")),
+            },
+            ')' => match groups.pop() {
+                Some((_, _, mut ends)) => {
+                    ends.append(&mut current);
+                    ends.sort();
+                    ends.dedup();
+                    current = ends;
+                }
+                None => return Err(regex.error_at(offset, "unmatched `)`")),
+            },
+            _ => return Err(regex.error_at(offset, format!("invalid direction `{}`", c))),
+        }
+    }
+    if let Some((offset, _, _)) = groups.last() {
+        return Err(regex.error_at(*offset, "unclosed `(`"));
+    }
+
+    Ok(map)
+}
+
+/// Returns the number of doors to pass through to reach the furthest room.
+pub fn part1(map: &Map) -> usize {
+    map.distances()
+        .iter()
+        .map(|(_, distance)| distance)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Returns the number of rooms that are at least 1000 doors away.
+pub fn part2(map: &Map) -> usize {
+    map.distances()
+        .iter()
+        .filter(|(_, distance)| *distance >= 1000)
+        .count()
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &Map) -> (usize, usize) {
+    (part1(input), part2(input))
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Map;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn furthest_room() {
+        let examples = [
+            ("^WNE$", 3),
+            ("^ENWWW(NEEE|SSE(EE|N))$", 10),
+            ("^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|NNN)$", 18),
+            ("^ESSWWN(E|NNENN(EESS(WNSE|)SSS|WWWSSSSE(SW|NNNE)))$", 23),
+            (
+                "^WSSEESWWWNW(S|NENNEEEENN(ESSSSW(NWSW|SSEN)|WSWWN(E|WWS(E|SS))))$",
+                31,
+            ),
+        ];
+        for (regex, doors) in examples.iter() {
+            assert_eq!(part1(&parse(regex).unwrap()), *doors, "{}", regex);
+        }
+    }
+
+    #[test]
+    fn render() {
+        let map = parse("^ENWWW(NEEE|SSE(EE|N))$").unwrap();
+        assert_eq!(
+            map.to_string(),
+            "#########
+#.|.|.|.#
+#-#######
+#.|.|.|.#
+#-#####-#
+#.#.#X|.#
+#-#-#####
+#.|.|.|.#
+#########
+"
+        );
+
+        let error = parse("^EN(W|S$").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 4: unclosed `(`");
+    }
+}
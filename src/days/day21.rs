@@ -0,0 +1,216 @@
+use crate::vm::{Exit, Machine, Opcode, Program, Value};
+use crate::{ParseError, Solution};
+use std::cell::OnceCell;
+use std::collections::HashSet;
+
+const NUM_REGISTERS: usize = 6;
+
+/// The instruction that compares a register with register 0 to decide whether the program halts.
+fn find_halting_test(program: &Program) -> Option<(usize, Value)> {
+    program
+        .instructions
+        .iter()
+        .enumerate()
+        .find_map(|(address, instruction)| match instruction.opcode {
+            Opcode::Eqrr if instruction.a == 0 => Some((address, instruction.b)),
+            Opcode::Eqrr if instruction.b == 0 => Some((address, instruction.a)),
+            _ => None,
+        })
+}
+
+/// A loop that divides a register by a constant by counting up until the next multiple is too
+/// large:
+///
+/// ```text
+/// seti 0 _ q
+/// addi q 1 t
+/// muli t divisor t
+/// gtrr t dividend t
+/// ```
+struct Division {
+    address: usize,
+    quotient: usize,
+    dividend: usize,
+    divisor: Value,
+}
+
+fn find_divisions(program: &Program) -> Vec<Division> {
+    program
+        .instructions
+        .windows(4)
+        .enumerate()
+        .filter_map(|(address, window)| {
+            let (init, next, multiply, test) = (window[0], window[1], window[2], window[3]);
+            let matches = init.opcode == Opcode::Seti
+                && init.a == 0
+                && next.opcode == Opcode::Addi
+                && next.a == init.c
+                && next.b == 1
+                && multiply.opcode == Opcode::Muli
+                && multiply.a == next.c
+                && multiply.c == next.c
+                && multiply.b > 0
+                && test.opcode == Opcode::Gtrr
+                && test.a == next.c;
+            if matches {
+                Some(Division {
+                    address,
+                    quotient: init.c as usize,
+                    dividend: test.b as usize,
+                    divisor: multiply.b,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Returns the values register 0 is compared with, in the order they first occur, until they
+/// start repeating. Setting register 0 to one of them makes the program halt when it is reached.
+///
+/// The division loops in the program are skipped by computing the quotient directly, which
+/// makes each value take a few dozen instructions instead of hundreds of thousands.
+pub fn halting_values(program: &Program) -> Vec<Value> {
+    let (test_address, test_register) =
+        find_halting_test(program).expect("the program never compares register 0");
+    let divisions = find_divisions(program);
+
+    let mut machine = Machine::new(program, NUM_REGISTERS);
+    // None of the values is negative, so the program never halts by itself
+    machine.registers[0] = -1;
+
+    let mut seen = HashSet::new();
+    let mut values = Vec::new();
+    loop {
+        let exit = machine
+            .run_until(None, |machine| {
+                machine.ip == test_address as Value
+                    || divisions.iter().any(|d| machine.ip == d.address as Value)
+            })
+            .expect("the program only uses valid registers");
+        if exit == Exit::Halted {
+            return values;
+        }
+
+        if let Some(division) = divisions.iter().find(|d| machine.ip == d.address as Value) {
+            let registers = &mut machine.registers;
+            registers[division.quotient] = registers[division.dividend] / division.divisor;
+            machine.ip += 1;
+            continue;
+        }
+
+        let value = machine.registers[test_register as usize];
+        if !seen.insert(value) {
+            return values;
+        }
+        values.push(value);
+        machine
+            .step()
+            .expect("the program only uses valid registers");
+    }
+}
+
+/// The program with the values of register 0 that halt it, which are found by running the
+/// program the first time a part needs them and then shared by both parts.
+pub struct Device {
+    pub program: Program,
+    halting_values: OnceCell<Vec<Value>>,
+}
+
+impl Device {
+    pub fn new(program: Program) -> Device {
+        Device {
+            program,
+            halting_values: OnceCell::new(),
+        }
+    }
+
+    /// Returns `halting_values` of the program.
+    pub fn halting_values(&self) -> &[Value] {
+        self.halting_values
+            .get_or_init(|| halting_values(&self.program))
+    }
+}
+
+pub fn parse(input: &str) -> Result<Device, ParseError> {
+    let program = Program::parse_with_registers(input, NUM_REGISTERS)?;
+    if find_halting_test(&program).is_none() {
+        return Err(ParseError::new(
+            1,
+            None,
+            "the program never compares register 0",
+        ));
+    }
+    Ok(Device::new(program))
+}
+
+/// Returns the value of register 0 that halts the program after the fewest instructions.
+pub fn part1(device: &Device) -> Value {
+    *device
+        .halting_values()
+        .first()
+        .expect("the program halts before comparing register 0")
+}
+
+/// Returns the value of register 0 that halts the program after the most instructions.
+pub fn part2(device: &Device) -> Value {
+    *device
+        .halting_values()
+        .last()
+        .expect("the program halts before comparing register 0")
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(device: &Device) -> (Value, Value) {
+    (part1(device), part2(device))
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Device;
+    type Part1 = Value;
+    type Part2 = Value;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn halting_values_match_direct_computation() {
+        let device = parse(include_str!("../../inputs/day21/input")).unwrap();
+        let values = device.halting_values();
+
+        // The same values computed by the program written out by hand
+        let mut expected = Vec::new();
+        let mut seen = HashSet::new();
+        let mut value = 0;
+        loop {
+            let mut input = value | 0x10000;
+            value = 10373714;
+            while input > 0 {
+                value = (((value + (input & 0xff)) & 0xffffff) * 65899) & 0xffffff;
+                input /= 256;
+            }
+            if !seen.insert(value) {
+                break;
+            }
+            expected.push(value);
+        }
+        assert_eq!(values, expected);
+    }
+}
@@ -0,0 +1,226 @@
+use crate::parse::{end_of_input, lines};
+use crate::{search, Bounds, Grid, ParseError, Point, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+    Rocky,
+    Wet,
+    Narrow,
+}
+
+impl Region {
+    pub fn risk_level(self) -> usize {
+        self as usize
+    }
+
+    fn allows(self, tool: Tool) -> bool {
+        match self {
+            Region::Rocky => tool != Tool::Neither,
+            Region::Wet => tool != Tool::Torch,
+            Region::Narrow => tool != Tool::ClimbingGear,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Tool {
+    Torch,
+    ClimbingGear,
+    Neither,
+}
+
+impl Tool {
+    const ALL: [Tool; 3] = [Tool::Torch, Tool::ClimbingGear, Tool::Neither];
+}
+
+const MINUTES_PER_MOVE: usize = 1;
+const MINUTES_PER_SWITCH: usize = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scan {
+    pub depth: usize,
+    pub target: Point,
+}
+
+/// The cave below the mouth. Erosion levels are only computed for the part of the cave that has
+/// been looked at, and the grid holding them grows as the search wanders further out.
+pub struct Cave {
+    scan: Scan,
+    erosion_levels: Grid<usize>,
+}
+
+impl Cave {
+    pub fn new(scan: Scan) -> Cave {
+        let mut cave = Cave {
+            scan,
+            erosion_levels: Grid::new(0, 0, 0),
+        };
+        cave.extend_to(scan.target);
+        cave
+    }
+
+    /// Grows the grid of erosion levels to include `position`, at least doubling it in size in
+    /// every direction that needs to grow.
+    fn extend_to(&mut self, position: Point) {
+        let old = &self.erosion_levels;
+        let (old_width, old_height) = (old.width(), old.height());
+        let width = (position.x as usize + 1).max(old_width);
+        let height = (position.y as usize + 1).max(old_height);
+        let width = if width > old_width {
+            width.max(old_width * 2)
+        } else {
+            width
+        };
+        let height = if height > old_height {
+            height.max(old_height * 2)
+        } else {
+            height
+        };
+
+        let mut levels = Grid::new(width, height, 0);
+        for point in levels.points() {
+            let level = match old.get(point) {
+                Some(level) => *level,
+                None => {
+                    let geologic_index = if point == Point::new(0, 0) || point == self.scan.target {
+                        0
+                    } else if point.y == 0 {
+                        point.x as usize * 16807
+                    } else if point.x == 0 {
+                        point.y as usize * 48271
+                    } else {
+                        levels[point - Point::new(1, 0)] * levels[point - Point::new(0, 1)]
+                    };
+                    (geologic_index + self.scan.depth) % 20183
+                }
+            };
+            levels[point] = level;
+        }
+        self.erosion_levels = levels;
+    }
+
+    /// Returns the type of the region at `position`, which must not be left or above the mouth.
+    pub fn region(&mut self, position: Point) -> Region {
+        if !self.erosion_levels.contains(position) {
+            self.extend_to(position);
+        }
+        match self.erosion_levels[position] % 3 {
+            0 => Region::Rocky,
+            1 => Region::Wet,
+            _ => Region::Narrow,
+        }
+    }
+
+    /// Returns the fewest minutes it takes to reach the target with the torch equipped, searching
+    /// over the position and the equipped tool.
+    pub fn fastest_rescue(&mut self) -> usize {
+        let start = (Point::new(0, 0), Tool::Torch);
+        let goal = (self.scan.target, Tool::Torch);
+        // Every remaining step takes at least a minute, and a switch back to the torch is needed
+        // if it isn't equipped
+        let heuristic = |(position, tool): &(Point, Tool)| {
+            let switch = if *tool == Tool::Torch {
+                0
+            } else {
+                MINUTES_PER_SWITCH
+            };
+            position.manhattan_distance(goal.0) as usize * MINUTES_PER_MOVE + switch
+        };
+        let path = search::a_star(
+            start,
+            |(position, tool)| {
+                let region = self.region(position);
+                let mut next = Vec::new();
+                for other in Tool::ALL
+                    .iter()
+                    .filter(|t| **t != tool && region.allows(**t))
+                {
+                    next.push(((position, *other), MINUTES_PER_SWITCH));
+                }
+                for neighbour in position.neighbours().iter() {
+                    if neighbour.x >= 0 && neighbour.y >= 0 && self.region(*neighbour).allows(tool)
+                    {
+                        next.push(((*neighbour, tool), MINUTES_PER_MOVE));
+                    }
+                }
+                next
+            },
+            heuristic,
+            |node| *node == goal,
+        );
+        path.expect("the target can always be reached").cost
+    }
+}
+
+pub fn parse(input: &str) -> Result<Scan, ParseError> {
+    let mut lines = lines(input);
+    let mut next_line = |expected: &str| {
+        lines.next().ok_or_else(|| {
+            ParseError::new(
+                end_of_input(input),
+                None,
+                format!("expected `{}`", expected),
+            )
+        })
+    };
+    let depth = next_line("depth: n")?.strip_prefix("depth: ")?.parse()?;
+    let target_line = next_line("target: x,y")?.strip_prefix("target: ")?;
+    let target: Point = target_line.parse()?;
+    if target.x < 0 || target.y < 0 {
+        return Err(target_line.error("the target must not be left or above the mouth"));
+    }
+    Ok(Scan { depth, target })
+}
+
+/// Returns the total risk level of the rectangle from the mouth to the target.
+pub fn part1(scan: &Scan) -> usize {
+    let mut cave = Cave::new(*scan);
+    let area = Bounds {
+        min: Point::new(0, 0),
+        max: scan.target,
+    };
+    area.points()
+        .map(|position| cave.region(position).risk_level())
+        .sum()
+}
+
+pub fn part2(scan: &Scan) -> usize {
+    Cave::new(*scan).fastest_rescue()
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &Scan) -> (usize, usize) {
+    (part1(input), part2(input))
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Scan;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let scan = parse("depth: 510\ntarget: 10,10").unwrap();
+        assert_eq!(part1(&scan), 114);
+        assert_eq!(part2(&scan), 45);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::parse::lines;
+use crate::{ParseError, Point3, Solution};
+use regex::Regex;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Nanobot {
+    pub position: Point3,
+    pub radius: i64,
+}
+
+impl Nanobot {
+    pub fn in_range(&self, point: Point3) -> bool {
+        self.position.manhattan_distance(point) <= self.radius
+    }
+}
+
+/// A cube of points, from `min` up to but not including `min + size` in every dimension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Cube {
+    min: Point3,
+    size: i64,
+}
+
+impl Cube {
+    /// Returns the Manhattan distance from `point` to the closest point in the cube.
+    fn distance_to(&self, point: Point3) -> i64 {
+        let axis = |p: i64, min: i64| {
+            if p < min {
+                min - p
+            } else if p > min + self.size - 1 {
+                p - (min + self.size - 1)
+            } else {
+                0
+            }
+        };
+        axis(point.x, self.min.x) + axis(point.y, self.min.y) + axis(point.z, self.min.z)
+    }
+
+    /// Returns the number of nanobots that have at least one point of the cube in range.
+    fn bots_in_range(&self, nanobots: &[Nanobot]) -> usize {
+        nanobots
+            .iter()
+            .filter(|bot| self.distance_to(bot.position) <= bot.radius)
+            .count()
+    }
+
+    /// Splits the cube into its 8 octants.
+    fn split(&self) -> Vec<Cube> {
+        let size = self.size / 2;
+        let mut octants = Vec::with_capacity(8);
+        for &x in [0, size].iter() {
+            for &y in [0, size].iter() {
+                for &z in [0, size].iter() {
+                    octants.push(Cube {
+                        min: self.min + Point3::new(x, y, z),
+                        size,
+                    });
+                }
+            }
+        }
+        octants
+    }
+}
+
+/// Finds the point in range of the most nanobots that is closest to the origin and returns its
+/// distance to the origin.
+///
+/// Starts with a cube containing all nanobots and keeps splitting the most promising cube: the
+/// one in range of the most nanobots, then the one closest to the origin. The number of nanobots
+/// in range of a cube is an upper bound for every point in it, so the first single point taken
+/// from the queue is the best one.
+pub fn best_position(nanobots: &[Nanobot]) -> i64 {
+    let (min, max) = nanobots.iter().fold(
+        (nanobots[0].position, nanobots[0].position),
+        |(min, max), bot| (min.min(&bot.position), max.max(&bot.position)),
+    );
+    let extent = (max - min).x.max((max - min).y).max((max - min).z) + 1;
+    let mut size = 1;
+    while size < extent {
+        size *= 2;
+    }
+
+    let origin = Point3::default();
+    let cube = Cube { min, size };
+    let mut queue = BinaryHeap::new();
+    queue.push((
+        cube.bots_in_range(nanobots),
+        Reverse(cube.distance_to(origin)),
+        Reverse(size),
+        cube,
+    ));
+    while let Some((_, Reverse(distance), _, cube)) = queue.pop() {
+        if cube.size == 1 {
+            return distance;
+        }
+        for octant in cube.split() {
+            queue.push((
+                octant.bots_in_range(nanobots),
+                Reverse(octant.distance_to(origin)),
+                Reverse(octant.size),
+                octant,
+            ));
+        }
+    }
+    unreachable!("the queue only runs empty after a single point was found")
+}
+
+pub fn parse(input: &str) -> Result<Vec<Nanobot>, ParseError> {
+    let regex = Regex::new(r"^pos=<(-?\d+),(-?\d+),(-?\d+)>, r=(\d+)$").unwrap();
+    let nanobots: Vec<Nanobot> = lines(input)
+        .map(|line| {
+            let captures = line.captures(&regex, "pos=<x,y,z>, r=n")?;
+            Ok(Nanobot {
+                position: Point3::new(captures.parse(1)?, captures.parse(2)?, captures.parse(3)?),
+                radius: captures.parse(4)?,
+            })
+        })
+        .collect::<Result<_, ParseError>>()?;
+
+    if nanobots.is_empty() {
+        return Err(ParseError::new(1, None, "expected at least one nanobot"));
+    }
+    Ok(nanobots)
+}
+
+/// Returns the number of nanobots in range of the nanobot with the largest signal radius.
+pub fn part1(nanobots: &[Nanobot]) -> usize {
+    let strongest = nanobots.iter().max_by_key(|bot| bot.radius).unwrap();
+    nanobots
+        .iter()
+        .filter(|bot| strongest.in_range(bot.position))
+        .count()
+}
+
+pub fn part2(nanobots: &[Nanobot]) -> i64 {
+    best_position(nanobots)
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[Nanobot]) -> (usize, i64) {
+    (part1(input), part2(input))
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Vec<Nanobot>;
+    type Part1 = usize;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        let nanobots = parse(
+            "pos=<0,0,0>, r=4
+pos=<1,0,0>, r=1
+pos=<4,0,0>, r=3
+pos=<0,2,0>, r=1
+pos=<0,5,0>, r=3
+pos=<0,0,3>, r=1
+pos=<1,1,1>, r=1
+pos=<1,1,2>, r=1
+pos=<1,3,1>, r=1",
+        )
+        .unwrap();
+        assert_eq!(part1(&nanobots), 7);
+
+        let nanobots = parse(
+            "pos=<10,12,12>, r=2
+pos=<12,14,12>, r=2
+pos=<16,12,12>, r=4
+pos=<14,14,14>, r=6
+pos=<50,50,50>, r=200
+pos=<10,10,10>, r=5",
+        )
+        .unwrap();
+        assert_eq!(part2(&nanobots), 36);
+    }
+}
@@ -0,0 +1,304 @@
+use crate::parse::{end_of_input, lines, Span};
+use crate::{battle, ParseError, Solution};
+use regex::Regex;
+use std::cmp::Reverse;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Army {
+    ImmuneSystem,
+    Infection,
+}
+
+#[derive(Debug, Clone)]
+pub struct Group {
+    pub army: Army,
+    pub units: u32,
+    pub hit_points: u32,
+    pub weaknesses: Vec<String>,
+    pub immunities: Vec<String>,
+    pub attack_damage: u32,
+    pub attack_type: String,
+    pub initiative: u32,
+}
+
+impl Group {
+    pub fn effective_power(&self) -> u64 {
+        u64::from(self.units) * u64::from(self.attack_damage)
+    }
+
+    /// Returns the damage this group would deal to `defender`.
+    pub fn damage_to(&self, defender: &Group) -> u64 {
+        if defender.immunities.contains(&self.attack_type) {
+            0
+        } else if defender.weaknesses.contains(&self.attack_type) {
+            self.effective_power() * 2
+        } else {
+            self.effective_power()
+        }
+    }
+}
+
+/// How a battle ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The army won with the given number of units left.
+    Won(Army, u32),
+    /// Neither army could kill a single unit of the other anymore.
+    Stalemate,
+}
+
+/// Lets each group pick the enemy group it deals the most damage to. Returns the index of the
+/// chosen target of every group.
+fn select_targets(groups: &[Group]) -> Vec<Option<usize>> {
+    let mut order: Vec<usize> = (0..groups.len()).collect();
+    order.sort_by_key(|&i| Reverse((groups[i].effective_power(), groups[i].initiative)));
+
+    let mut targets = vec![None; groups.len()];
+    let mut targeted = vec![false; groups.len()];
+    for attacker in order {
+        let attacking = &groups[attacker];
+        let target = (0..groups.len())
+            .filter(|&i| groups[i].army != attacking.army && !targeted[i])
+            .filter(|&i| attacking.damage_to(&groups[i]) > 0)
+            .max_by_key(|&i| {
+                let defender = &groups[i];
+                (
+                    attacking.damage_to(defender),
+                    defender.effective_power(),
+                    defender.initiative,
+                )
+            });
+        if let Some(target) = target {
+            targeted[target] = true;
+            targets[attacker] = Some(target);
+        }
+    }
+    targets
+}
+
+/// Fights the battle with the immune system's attacks boosted by `boost`.
+pub fn fight(groups: &[Group], boost: u32) -> Outcome {
+    let mut groups = groups.to_vec();
+    for group in groups.iter_mut() {
+        if group.army == Army::ImmuneSystem {
+            group.attack_damage = group.attack_damage.saturating_add(boost);
+        }
+    }
+
+    loop {
+        let armies_left = |army| groups.iter().any(|g| g.army == army);
+        match (
+            armies_left(Army::ImmuneSystem),
+            armies_left(Army::Infection),
+        ) {
+            (true, true) => {}
+            (immune_system, _) => {
+                let winner = if immune_system {
+                    Army::ImmuneSystem
+                } else {
+                    Army::Infection
+                };
+                return Outcome::Won(winner, groups.iter().map(|g| g.units).sum());
+            }
+        }
+
+        let targets = select_targets(&groups);
+        let mut order: Vec<usize> = (0..groups.len()).collect();
+        order.sort_by_key(|&i| Reverse(groups[i].initiative));
+
+        let mut units_killed = 0;
+        for attacker in order {
+            let target = match targets[attacker] {
+                Some(target) if groups[attacker].units > 0 => target,
+                _ => continue,
+            };
+            let damage = groups[attacker].damage_to(&groups[target]);
+            let defender = &mut groups[target];
+            let killed =
+                (damage / u64::from(defender.hit_points)).min(u64::from(defender.units)) as u32;
+            defender.units -= killed;
+            units_killed += killed;
+        }
+        if units_killed == 0 {
+            return Outcome::Stalemate;
+        }
+        groups.retain(|g| g.units > 0);
+    }
+}
+
+/// Parses the part of a group description in parentheses, e.g.
+/// `weak to radiation, bludgeoning; immune to fire`.
+fn parse_modifiers(span: Span, group: &mut Group) -> Result<(), ParseError> {
+    for modifier in span.split("; ") {
+        let (list, rest) = if let Ok(rest) = modifier.strip_prefix("weak to ") {
+            (&mut group.weaknesses, rest)
+        } else if let Ok(rest) = modifier.strip_prefix("immune to ") {
+            (&mut group.immunities, rest)
+        } else {
+            return Err(modifier.error("expected `weak to` or `immune to`"));
+        };
+        list.extend(rest.split(", ").map(|t| t.text.to_owned()));
+    }
+    Ok(())
+}
+
+pub fn parse(input: &str) -> Result<Vec<Group>, ParseError> {
+    let regex = Regex::new(
+        r"^(\d+) units each with (\d+) hit points (?:\(([^)]*)\) )?with an attack that does (\d+) (\w+) damage at initiative (\d+)$",
+    )
+    .unwrap();
+
+    let mut groups = Vec::new();
+    let mut army = None;
+    for line in lines(input) {
+        match line.text {
+            "" => continue,
+            "Immune System:" => army = Some(Army::ImmuneSystem),
+            "Infection:" => army = Some(Army::Infection),
+            _ => {
+                let army = army.ok_or_else(|| line.error("expected `Immune System:`"))?;
+                let captures = line.captures(
+                    &regex,
+                    "n units each with n hit points (...) with an attack that does n type damage at initiative n",
+                )?;
+                let mut group = Group {
+                    army,
+                    units: captures.parse(1)?,
+                    hit_points: captures.parse(2)?,
+                    weaknesses: Vec::new(),
+                    immunities: Vec::new(),
+                    attack_damage: captures.parse(4)?,
+                    attack_type: captures.str(5).to_owned(),
+                    initiative: captures.parse(6)?,
+                };
+                if group.hit_points == 0 {
+                    return Err(captures
+                        .get(2)
+                        .unwrap()
+                        .error("expected positive hit points"));
+                }
+                if let Some(modifiers) = captures.get(3) {
+                    parse_modifiers(modifiers, &mut group)?;
+                }
+                groups.push(group);
+            }
+        }
+    }
+
+    for army in [Army::ImmuneSystem, Army::Infection].iter() {
+        if !groups.iter().any(|g| g.army == *army) {
+            return Err(ParseError::new(
+                end_of_input(input),
+                None,
+                format!("expected at least one group in the {:?} army", army),
+            ));
+        }
+    }
+    Ok(groups)
+}
+
+/// Returns the number of units the winning army has left.
+pub fn part1(groups: &[Group]) -> u32 {
+    match fight(groups, 0) {
+        Outcome::Won(_, units) => units,
+        Outcome::Stalemate => panic!("the battle ended in a stalemate"),
+    }
+}
+
+/// Returns a boost above which the battle no longer changes. With at least this boost every
+/// attack of the immune system wipes out the group it targets, and its groups outrank all
+/// infection groups and each other in the same order whatever the boost.
+pub fn max_boost(groups: &[Group]) -> u32 {
+    let army = |army| groups.iter().filter(move |g| g.army == army);
+    let killing_blow = army(Army::Infection)
+        .filter(|defender| army(Army::ImmuneSystem).any(|a| a.damage_to(defender) > 0))
+        .map(|defender| u64::from(defender.units) * u64::from(defender.hit_points));
+    let effective_power = groups.iter().map(Group::effective_power);
+    killing_blow
+        .chain(effective_power)
+        .max()
+        .unwrap_or(0)
+        .min(i32::MAX as u64) as u32
+}
+
+/// Returns the number of units the immune system has left with the smallest boost that lets it
+/// win, or `None` if no boost does. Some boosts larger than a winning boost end in a stalemate,
+/// so every boost up to `max_boost` is tried, several at once in parallel.
+pub fn min_winning_boost(groups: &[Group]) -> Option<(u32, u32)> {
+    let boosts = 0..=max_boost(groups) as i32;
+    let (boost, units) =
+        battle::min_boost_parallel(boosts, battle::available_threads(), |boost| {
+            match fight(groups, boost as u32) {
+                Outcome::Won(Army::ImmuneSystem, units) => Some(units),
+                _ => None,
+            }
+        })?;
+    Some((boost as u32, units))
+}
+
+/// Returns the number of units the immune system has left with the smallest boost that lets it
+/// win.
+pub fn part2(groups: &[Group]) -> u32 {
+    min_winning_boost(groups)
+        .expect("no boost lets the immune system win")
+        .1
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[Group]) -> (u32, u32) {
+    (part1(input), part2(input))
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Vec<Group>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "Immune System:
+17 units each with 5390 hit points (weak to radiation, bludgeoning) with an attack that does 4507 fire damage at initiative 2
+989 units each with 1274 hit points (immune to fire; weak to bludgeoning, slashing) with an attack that does 25 slashing damage at initiative 3
+
+Infection:
+801 units each with 4706 hit points (weak to radiation) with an attack that does 116 bludgeoning damage at initiative 1
+4485 units each with 2961 hit points (immune to radiation; weak to fire, cold) with an attack that does 12 slashing damage at initiative 4
+";
+
+    #[test]
+    fn example() {
+        let groups = parse(EXAMPLE).unwrap();
+        assert_eq!(groups[1].immunities, vec!["fire"]);
+        assert_eq!(fight(&groups, 0), Outcome::Won(Army::Infection, 5216));
+        assert_eq!(fight(&groups, 1570), Outcome::Won(Army::ImmuneSystem, 51));
+        assert_eq!(part2(&groups), 51);
+        assert_eq!(min_winning_boost(&groups), Some((1570, 51)));
+
+        // The infection is immune to everything the immune system attacks with
+        let mut groups = groups;
+        for group in groups.iter_mut() {
+            if group.army == Army::Infection {
+                group.immunities = vec!["fire".to_string(), "slashing".to_string()];
+            }
+        }
+        assert_eq!(max_boost(&groups), 801 * 116);
+        assert_eq!(min_winning_boost(&groups), None);
+    }
+}
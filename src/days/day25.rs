@@ -0,0 +1,113 @@
+use crate::parse::lines;
+use crate::{ParseError, Solution, UnionFind};
+
+/// A point in 4D space.
+pub type Point4 = [i32; 4];
+
+/// Points at most this far apart belong to the same constellation.
+const MAX_DISTANCE: i32 = 3;
+
+fn manhattan_distance(a: &Point4, b: &Point4) -> i32 {
+    a.iter().zip(b.iter()).map(|(a, b)| (a - b).abs()).sum()
+}
+
+/// Groups the points into constellations, chains of points that are each within 3 of the next.
+pub fn constellations(points: &[Point4]) -> UnionFind {
+    let mut sets = UnionFind::new(points.len());
+    for (i, a) in points.iter().enumerate() {
+        for (j, b) in points.iter().enumerate().skip(i + 1) {
+            if manhattan_distance(a, b) <= MAX_DISTANCE {
+                sets.union(i, j);
+            }
+        }
+    }
+    sets
+}
+
+pub fn parse(input: &str) -> Result<Vec<Point4>, ParseError> {
+    lines(input)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let coords: Vec<i32> = line
+                .split(",")
+                .map(|c| c.trim().parse())
+                .collect::<Result<_, ParseError>>()?;
+            match coords[..] {
+                [x, y, z, w] => Ok([x, y, z, w]),
+                _ => Err(line.error("expected `x,y,z,w`")),
+            }
+        })
+        .collect()
+}
+
+pub fn part1(points: &[Point4]) -> usize {
+    constellations(points).sets()
+}
+
+/// There is no second puzzle on the last day.
+pub fn part2(_points: &[Point4]) -> &'static str {
+    "-"
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[Point4]) -> (usize, &'static str) {
+    (part1(input), part2(input))
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = Vec<Point4>;
+    type Part1 = usize;
+    type Part2 = &'static str;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        let points = parse(
+            "-1,2,2,0
+0,0,2,-2
+0,0,0,-2
+-1,2,0,0
+-2,-2,-2,2
+3,0,2,-1
+-1,3,2,2
+-1,0,-1,0
+0,2,1,-2
+3,0,0,0",
+        )
+        .unwrap();
+        assert_eq!(part1(&points), 4);
+
+        let points = parse(
+            "1,-1,-1,-2
+-2,-2,0,1
+0,2,1,3
+-2,3,-2,1
+0,2,3,-2
+-1,-1,1,-2
+0,-2,-1,0
+-2,2,3,-1
+1,2,2,0
+-1,-2,0,-2",
+        )
+        .unwrap();
+        assert_eq!(part1(&points), 8);
+    }
+}
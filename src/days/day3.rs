@@ -0,0 +1,104 @@
+use crate::parse::lines;
+use crate::{Grid, ParseError, Point, Solution};
+
+pub const FABRIC_SIZE: usize = 1000;
+
+pub struct Claim {
+    pub id: usize,
+    pub left: usize,
+    pub top: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Claim {
+    /// Returns the positions of all square inches covered by the claim.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (left, top) = (self.left as i32, self.top as i32);
+        let (width, height) = (self.width as i32, self.height as i32);
+        (top..top + height).flat_map(move |y| (left..left + width).map(move |x| Point::new(x, y)))
+    }
+}
+
+pub fn is_unique(claim: &Claim, fabric: &Grid<u32>) -> bool {
+    claim.points().all(|point| fabric[point] <= 1)
+}
+
+/// Returns for every square inch of fabric how many claims use it.
+pub fn claim_fabric(claims: &[Claim]) -> Grid<u32> {
+    let mut fabric = Grid::new(FABRIC_SIZE, FABRIC_SIZE, 0_u32);
+    for claim in claims.iter() {
+        for point in claim.points() {
+            fabric[point] += 1;
+        }
+    }
+    fabric
+}
+
+pub fn parse(input: &str) -> Result<Vec<Claim>, ParseError> {
+    // Parse the input into claims
+    let re = regex::Regex::new(r"^#(\d+) @ (\d+),(\d+): (\d+)x(\d+)$").unwrap();
+    let claims: Vec<Claim> = lines(input)
+        .map(|l| {
+            let captures = l.captures(&re, "#id @ left,top: widthxheight")?;
+            let claim = Claim {
+                id: captures.parse(1)?,
+                left: captures.parse(2)?,
+                top: captures.parse(3)?,
+                width: captures.parse(4)?,
+                height: captures.parse(5)?,
+            };
+            if claim.left + claim.width > FABRIC_SIZE || claim.top + claim.height > FABRIC_SIZE {
+                return Err(l.error(format!(
+                    "claim does not fit on the {0}x{0} fabric",
+                    FABRIC_SIZE
+                )));
+            }
+            Ok(claim)
+        })
+        .collect::<Result<_, _>>()?;
+    if claims.is_empty() {
+        return Err(ParseError::new(1, None, "expected at least one claim"));
+    }
+    Ok(claims)
+}
+
+pub fn part1(claims: &[Claim]) -> usize {
+    // Count the number of inches that have more than 1 claim
+    claim_fabric(claims).values().filter(|s| **s > 1).count()
+}
+
+pub fn part2(claims: &[Claim]) -> usize {
+    // Find the claim location were all entries are 1
+    let fabric = claim_fabric(claims);
+    claims
+        .iter()
+        .find(|claim| is_unique(claim, &fabric))
+        .map(|claim| claim.id)
+        .expect("No unique claim found")
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[Claim]) -> (usize, usize) {
+    (part1(input), part2(input))
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Claim>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
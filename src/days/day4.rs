@@ -0,0 +1,177 @@
+use crate::parse::{end_of_input, lines};
+use crate::{ParseError, Solution};
+use chrono::offset::TimeZone;
+use chrono::Timelike;
+use std::collections::HashMap;
+use std::ops::Range;
+
+enum GuardActivity {
+    WakesUp,
+    FallsAsleep,
+    BeginsShift(usize),
+}
+
+enum GuardState {
+    Awake,
+    Asleep(u32),
+}
+
+pub type SleepRanges = HashMap<usize, Vec<Range<u32>>>;
+
+pub fn build_sleep_histogram(ranges: &[Range<u32>]) -> [u32; 60] {
+    let mut histogram = [0; 60];
+    for range in ranges.iter() {
+        for minute in range.start..range.end {
+            histogram[minute as usize] += 1;
+        }
+    }
+    histogram
+}
+
+pub fn parse(input: &str) -> Result<SleepRanges, ParseError> {
+    // Get the input sort and in a machine readable format.
+    let re = regex::Regex::new(
+        r"^\[(\d+)-(\d+)-(\d+) (\d+):(\d+)] ((falls asleep)|(wakes up)|(Guard #(\d+) begins shift))$",
+    )
+    .unwrap();
+    let events = {
+        let mut events = lines(input)
+            .map(|l| {
+                let capture = l.captures(&re, "[yyyy-mm-dd hh:mm] event")?;
+                let (hour, minute) = (capture.parse(4)?, capture.parse(5)?);
+                let date = chrono::Utc
+                    .ymd_opt(capture.parse(1)?, capture.parse(2)?, capture.parse(3)?)
+                    .single()
+                    .and_then(|date| date.and_hms_opt(hour, minute, 0))
+                    .ok_or_else(|| l.error("invalid date"))?;
+                let activity = match capture.str(6) {
+                    "wakes up" => GuardActivity::WakesUp,
+                    "falls asleep" => GuardActivity::FallsAsleep,
+                    _ => GuardActivity::BeginsShift(capture.parse(10)?),
+                };
+                Ok((date, activity, l))
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+
+        // Sort the events by date
+        events.sort_by_key(|(date, _, _)| *date);
+        events
+    };
+
+    // Convert the input into a map of sleep ranges
+    let mut sleep_ranges: SleepRanges = HashMap::new();
+    let mut current_guard_id = None;
+    let mut current_guard_state = GuardState::Awake;
+    for (date, activity, line) in events.iter() {
+        match activity {
+            GuardActivity::BeginsShift(id) => {
+                if let GuardState::Asleep(_) = current_guard_state {
+                    return Err(line.error("shift begins while the previous guard is asleep"));
+                }
+                current_guard_id = Some(*id);
+                current_guard_state = GuardState::Awake;
+            }
+            GuardActivity::FallsAsleep => match (current_guard_id, &current_guard_state) {
+                (Some(_), GuardState::Awake) => {
+                    current_guard_state = GuardState::Asleep(date.time().minute());
+                }
+                (None, _) => return Err(line.error("guard falls asleep before any shift")),
+                _ => return Err(line.error("guard falls asleep while already asleep")),
+            },
+            GuardActivity::WakesUp => match (current_guard_id, &current_guard_state) {
+                (Some(id), GuardState::Asleep(time)) => {
+                    sleep_ranges
+                        .entry(id)
+                        .or_default()
+                        .push(*time..date.time().minute());
+                    current_guard_state = GuardState::Awake;
+                }
+                _ => return Err(line.error("guard wakes up without being asleep")),
+            },
+        }
+    }
+
+    let end = end_of_input(input);
+    if let GuardState::Asleep(_) = current_guard_state {
+        return Err(ParseError::new(end, None, "guard never wakes up"));
+    }
+    if sleep_ranges.is_empty() {
+        return Err(ParseError::new(
+            end,
+            None,
+            "expected a guard to fall asleep",
+        ));
+    }
+    Ok(sleep_ranges)
+}
+
+pub fn part1(sleep_ranges: &SleepRanges) -> usize {
+    let most_asleep_guard = sleep_ranges
+        .iter()
+        .map(|entry| (entry.0, entry.1.iter().fold(0, |s, r| s + r.end - r.start)))
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+        .unwrap()
+        .0;
+
+    let max_minute = sleep_ranges
+        .get(most_asleep_guard)
+        .map(|ranges| build_sleep_histogram(ranges))
+        .map(|histogram| {
+            histogram
+                .iter()
+                .enumerate()
+                .max_by(|(_, a), (_, b)| a.cmp(b))
+                .unwrap()
+                .0
+        })
+        .unwrap();
+
+    max_minute * most_asleep_guard
+}
+
+pub fn part2(sleep_ranges: &SleepRanges) -> usize {
+    let most_frequent_guard = sleep_ranges
+        .iter()
+        .map(|(id, range)| (id, build_sleep_histogram(range)))
+        .map(|(id, histogram)| {
+            (
+                id,
+                histogram
+                    .iter()
+                    .enumerate()
+                    .max_by(|(_, a), (_, b)| a.cmp(b))
+                    .map(|(id, count)| (id, *count))
+                    .unwrap(),
+            )
+        })
+        .max_by(|(_, (_, a)), (_, (_, b))| a.cmp(b))
+        .map(|(id, (minute, _))| (id, minute))
+        .unwrap();
+
+    *most_frequent_guard.0 * most_frequent_guard.1
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &SleepRanges) -> (usize, usize) {
+    (part1(input), part2(input))
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = SleepRanges;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
@@ -0,0 +1,70 @@
+use crate::parse::lines;
+use crate::{ParseError, Solution};
+
+pub fn react_full<'a, T: Iterator<Item = &'a u8>>(polymer: T) -> Vec<u8> {
+    let mut value: Vec<u8> = polymer.copied().collect();
+    let mut i = 0_usize;
+    loop {
+        if i + 1 >= value.len() {
+            return value;
+        }
+
+        if value[i] + 32 == value[i + 1] || value[i] - 32 == value[i + 1] {
+            value.remove(i);
+            value.remove(i);
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<u8>, ParseError> {
+    let polymer = lines(input)
+        .next()
+        .ok_or_else(|| ParseError::new(1, None, "expected a polymer"))?
+        .trim();
+    match polymer
+        .char_indices()
+        .find(|(_, c)| !c.is_ascii_alphabetic())
+    {
+        Some((offset, c)) => Err(polymer.error_at(offset, format!("invalid unit `{}`", c))),
+        None => Ok(polymer.text.as_bytes().to_vec()),
+    }
+}
+
+pub fn part1(polymer: &[u8]) -> usize {
+    react_full(polymer.iter()).len()
+}
+
+pub fn part2(polymer: &[u8]) -> usize {
+    (97_u8..123_u8)
+        .map(|idx| react_full(polymer.iter().filter(|p| **p != idx && **p != idx - 32)).len())
+        .min()
+        .unwrap()
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[u8]) -> (usize, usize) {
+    (part1(input), part2(input))
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<u8>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
@@ -0,0 +1,88 @@
+use crate::parse::lines;
+use crate::search;
+use crate::{Bounds, ParseError, Point, Solution};
+
+/// Returns the bounds of the given points.
+fn bounds(input: &[Point]) -> Bounds {
+    Bounds::from_points(input.iter().copied()).expect("no coordinates")
+}
+
+pub fn parse(input: &str) -> Result<Vec<Point>, ParseError> {
+    let points: Vec<Point> = lines(input).map(|l| l.parse()).collect::<Result<_, _>>()?;
+    if points.is_empty() {
+        return Err(ParseError::new(1, None, "expected at least one coordinate"));
+    }
+    Ok(points)
+}
+
+pub fn part1(input: &[Point]) -> usize {
+    let bounds = bounds(input);
+
+    // Build a voronoi by flood filling the bounds from all points at once
+    let voronoi = search::bfs_multi(input.iter().copied(), |p: Point| {
+        p.neighbours()
+            .to_vec()
+            .into_iter()
+            .filter(move |n| bounds.contains(*n))
+    });
+
+    let mut areas = vec![0; input.len()];
+    for location in bounds.points() {
+        if let Some(idx) = voronoi.source(&location) {
+            areas[idx] += 1;
+        }
+    }
+
+    let largest_finite_area = areas
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| !bounds.on_edge(input[*idx]))
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+        .unwrap();
+
+    *largest_finite_area.1
+}
+
+pub fn part2(input: &[Point]) -> usize {
+    let mut count = 0;
+    for location in bounds(input).points() {
+        let mut total_distance = 0;
+        for point in input.iter() {
+            total_distance += location.manhattan_distance(*point);
+            if total_distance >= 10000 {
+                break;
+            }
+        }
+
+        if total_distance < 10000 {
+            count += 1;
+        }
+    }
+
+    count
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[Point]) -> (usize, usize) {
+    (part1(input), part2(input))
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<Point>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
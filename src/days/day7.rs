@@ -0,0 +1,203 @@
+use crate::parse::lines;
+use crate::{ParseError, Solution};
+use std::cell::RefCell;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+struct Step {
+    char: String,
+    followup_steps: Vec<Rc<RefCell<Step>>>,
+    before_step_count: usize,
+}
+
+impl Step {
+    fn new(char: String) -> Step {
+        Step {
+            char,
+            followup_steps: Vec::new(),
+            before_step_count: 0,
+        }
+    }
+}
+
+impl PartialEq for Step {
+    fn eq(&self, other: &Self) -> bool {
+        other.char == self.char
+    }
+}
+
+impl PartialOrd for Step {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Eq for Step {}
+
+impl Ord for Step {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.char.cmp(&self.char)
+    }
+}
+
+type StepQueue = BinaryHeap<Rc<RefCell<Step>>>;
+
+/// A requirement that the first step must be finished before the second step can begin.
+pub type Dependency = (String, String);
+
+fn build_queue(dependencies: &[Dependency]) -> StepQueue {
+    // Build the graph
+    let steps: Vec<Rc<RefCell<Step>>> = {
+        let mut steps = HashMap::new();
+        for (a, b) in dependencies.iter() {
+            let step_b = steps
+                .entry(b.clone())
+                .or_insert_with(|| Rc::new(RefCell::new(Step::new(b.clone()))))
+                .clone();
+            let step_a = steps
+                .entry(a.clone())
+                .or_insert_with(|| Rc::new(RefCell::new(Step::new(a.clone()))));
+            step_b.borrow_mut().before_step_count += 1;
+            step_a.borrow_mut().followup_steps.push(step_b);
+        }
+        steps.into_values().collect()
+    };
+
+    // Build up the queue by finding all root steps
+    steps
+        .iter()
+        .filter(|f| f.borrow().before_step_count == 0)
+        .cloned()
+        .collect()
+}
+
+fn work_done(step: &Rc<RefCell<Step>>, queue: &mut StepQueue) {
+    let step = step.borrow();
+
+    // Update all next steps
+    for next_step in step.followup_steps.iter() {
+        {
+            let mut next_step_ref = next_step.borrow_mut();
+            next_step_ref.before_step_count -= 1;
+            if next_step_ref.before_step_count != 0 {
+                continue;
+            }
+        }
+        queue.push(next_step.clone());
+    }
+}
+
+#[derive(Clone)]
+enum Worker {
+    Idle,
+    Working(Rc<RefCell<Step>>, u32),
+}
+
+impl Worker {
+    fn put_to_work(step: Rc<RefCell<Step>>) -> Worker {
+        let time = 60 + step.borrow().char.as_bytes()[0] - 64;
+        Worker::Working(step, u32::from(time))
+    }
+
+    fn is_idle(&self) -> bool {
+        matches!(self, Worker::Idle)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Dependency>, ParseError> {
+    let re = regex::Regex::new(r"^Step ([A-Z]) must be finished before step ([A-Z]) can begin\.$")
+        .unwrap();
+    lines(input)
+        .map(|l| {
+            let capture = l.captures(&re, "Step A must be finished before step B can begin.")?;
+            Ok((capture.str(1).to_owned(), capture.str(2).to_owned()))
+        })
+        .collect()
+}
+
+pub fn part1(dependencies: &[Dependency]) -> String {
+    let mut queue = build_queue(dependencies);
+    let mut order = String::new();
+
+    // Now iterate over the queue (basically bread first search)
+    while let Some(step) = queue.pop() {
+        order.push_str(&step.borrow().char);
+        work_done(&step, &mut queue);
+    }
+
+    order
+}
+
+pub fn part2(dependencies: &[Dependency]) -> u32 {
+    let mut queue = build_queue(dependencies);
+    let mut workers = [
+        Worker::Idle,
+        Worker::Idle,
+        Worker::Idle,
+        Worker::Idle,
+        Worker::Idle,
+    ];
+    let mut total_time = 0;
+
+    // As long as there are steps to complete ..
+    while !queue.is_empty() || !workers.iter().all(|w| w.is_idle()) {
+        // Try to assign all idle workers
+        for worker in workers.iter_mut().filter(|w| w.is_idle()) {
+            if let Some(work) = queue.pop() {
+                *worker = Worker::put_to_work(work);
+            } else {
+                break;
+            }
+        }
+
+        // Skip time until a worker is done
+        let skip_time = workers
+            .iter()
+            .filter_map(|w| match w {
+                Worker::Idle => None,
+                Worker::Working(_, b) => Some(*b),
+            })
+            .min()
+            .unwrap();
+
+        for worker in workers.iter_mut() {
+            if let Worker::Working(work, time) = worker {
+                *time -= skip_time;
+                if *time == 0 {
+                    work_done(work, &mut queue);
+                    *worker = Worker::Idle;
+                }
+            }
+        }
+
+        total_time += skip_time;
+    }
+
+    total_time
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[Dependency]) -> (String, u32) {
+    (part1(input), part2(input))
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<Dependency>;
+    type Part1 = String;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
@@ -0,0 +1,124 @@
+use crate::parse::{end_of_input, lines, Span};
+use crate::{ParseError, Solution};
+
+pub fn sum_meta_entries<'a, T>(it: &mut T) -> u32
+where
+    T: Iterator<Item = &'a u32>,
+{
+    let child_count = it.next().unwrap();
+    let meta_entries_count = it.next().unwrap();
+
+    let mut meta_entries_sum = 0;
+    for _ in 0..*child_count {
+        meta_entries_sum += sum_meta_entries(it);
+    }
+
+    for _ in 0..*meta_entries_count {
+        meta_entries_sum += it.next().unwrap();
+    }
+
+    meta_entries_sum
+}
+
+pub fn compute_value<'a, T>(it: &mut T) -> u32
+where
+    T: Iterator<Item = &'a u32>,
+{
+    let child_count = it.next().unwrap();
+    let meta_entries_count = it.next().unwrap();
+
+    if *child_count == 0 {
+        // If there are no simply sum the meta entries together
+        let mut meta_entries_sum = 0;
+        for _ in 0..*meta_entries_count {
+            meta_entries_sum += it.next().unwrap();
+        }
+        meta_entries_sum
+    } else {
+        // If there are children, compute their root value
+        let mut child_values = Vec::with_capacity(*child_count as usize);
+        for _ in 0..*child_count {
+            child_values.push(compute_value(it));
+        }
+
+        // Use the meta entries as references
+        let mut meta_entries_sum = 0;
+        for _ in 0..*meta_entries_count {
+            let meta_entry = it.next().unwrap();
+            if *meta_entry > 0 && *meta_entry as usize <= child_values.len() {
+                meta_entries_sum += child_values[(meta_entry - 1) as usize];
+            }
+        }
+
+        meta_entries_sum
+    }
+}
+
+/// Returns the index just past the node starting at `start`, or `None` if the numbers end
+/// before the node does.
+fn node_end(numbers: &[u32], start: usize) -> Option<usize> {
+    let child_count = *numbers.get(start)?;
+    let meta_entries_count = *numbers.get(start + 1)? as usize;
+    let mut end = start + 2;
+    for _ in 0..child_count {
+        end = node_end(numbers, end)?;
+    }
+    end += meta_entries_count;
+    if end <= numbers.len() {
+        Some(end)
+    } else {
+        None
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+    let spans: Vec<Span> = lines(input).flat_map(|l| l.split_whitespace()).collect();
+    let numbers = spans
+        .iter()
+        .map(|d| d.parse())
+        .collect::<Result<Vec<u32>, _>>()?;
+
+    // The numbers must form exactly one tree
+    match node_end(&numbers, 0) {
+        Some(end) if end == numbers.len() => Ok(numbers),
+        Some(end) => Err(spans[end].error("expected the end of the tree")),
+        None => Err(ParseError::new(
+            end_of_input(input),
+            None,
+            "the tree ends too early",
+        )),
+    }
+}
+
+pub fn part1(input: &[u32]) -> u32 {
+    sum_meta_entries(&mut input.iter())
+}
+
+pub fn part2(input: &[u32]) -> u32 {
+    compute_value(&mut input.iter())
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[u32]) -> (u32, u32) {
+    (part1(input), part2(input))
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<u32>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
@@ -0,0 +1,128 @@
+use crate::parse::lines;
+use crate::{ParseError, Solution};
+use std::collections::VecDeque;
+
+/// Circle implements a circular vector with the help of a `VecDeque`. Insertion and removal is fast
+/// at the beginning of the `VecDeque` so the cursor is always kept at the beginning of the
+/// `VecDeque`.
+struct Circle {
+    vec: VecDeque<usize>,
+}
+
+impl Circle {
+    pub fn new(last_marble_value: usize) -> Circle {
+        let mut vec = VecDeque::with_capacity(last_marble_value + 1);
+        vec.push_back(0);
+        Circle { vec }
+    }
+
+    pub fn move_counter_clockwise(&mut self, count: usize) {
+        for _ in 0..count {
+            let val = self.vec.pop_back().unwrap();
+            self.vec.push_front(val);
+        }
+    }
+
+    pub fn move_clockwise(&mut self, count: usize) {
+        for _ in 0..count {
+            let val = self.vec.pop_front().unwrap();
+            self.vec.push_back(val);
+        }
+    }
+
+    pub fn insert(&mut self, value: usize) {
+        self.vec.push_front(value);
+    }
+
+    pub fn remove(&mut self) -> Option<usize> {
+        self.vec.pop_front()
+    }
+}
+
+pub fn find_highest_score(player_count: usize, last_marble_value: usize) -> usize {
+    let mut player_scores = vec![0; player_count];
+
+    let mut circle = Circle::new(last_marble_value);
+
+    for turn in 1..=last_marble_value {
+        if turn % 23 == 0 {
+            circle.move_counter_clockwise(7);
+            let score = turn + circle.remove().unwrap();
+            let player = (turn - 1) % player_scores.len();
+            player_scores[player] += score;
+        } else {
+            circle.move_clockwise(2);
+            circle.insert(turn);
+        }
+    }
+
+    *player_scores.iter().max().unwrap()
+}
+
+pub fn parse(input: &str) -> Result<(usize, usize), ParseError> {
+    let regex = regex::Regex::new(r"^(\d+) players; last marble is worth (\d+) points$").unwrap();
+    let line = lines(input)
+        .next()
+        .ok_or_else(|| ParseError::new(1, None, "expected the game description"))?;
+    let captures = line.trim().captures(
+        &regex,
+        "<players> players; last marble is worth <points> points",
+    )?;
+    let player_count: usize = captures.parse(1)?;
+    if player_count == 0 {
+        return Err(captures
+            .get(1)
+            .unwrap()
+            .error("there must be at least one player"));
+    }
+    Ok((player_count, captures.parse(2)?))
+}
+
+pub fn part1(&(player_count, last_marble_value): &(usize, usize)) -> usize {
+    find_highest_score(player_count, last_marble_value)
+}
+
+pub fn part2(&(player_count, last_marble_value): &(usize, usize)) -> usize {
+    find_highest_score(player_count, last_marble_value * 100)
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &(usize, usize)) -> (usize, usize) {
+    (part1(input), part2(input))
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    /// The number of players and the value of the last marble.
+    type Input = (usize, usize);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test() {
+        assert_eq!(find_highest_score(9, 25), 32);
+        assert_eq!(find_highest_score(10, 1618), 8317);
+        assert_eq!(find_highest_score(13, 7999), 146373);
+        assert_eq!(find_highest_score(17, 1104), 2764);
+        assert_eq!(find_highest_score(21, 6111), 54718);
+        assert_eq!(find_highest_score(30, 5807), 37305);
+    }
+}
@@ -0,0 +1,77 @@
+use crate::solution::solve_timed;
+use crate::Puzzle;
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+/// All solved puzzles, ordered by day.
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle::new(1, "Chronal Calibration", solve_timed::<day1::Day1>),
+    Puzzle::new(2, "Inventory Management System", solve_timed::<day2::Day2>),
+    Puzzle::new(3, "No Matter How You Slice It", solve_timed::<day3::Day3>),
+    Puzzle::new(4, "Repose Record", solve_timed::<day4::Day4>),
+    Puzzle::new(5, "Alchemical Reduction", solve_timed::<day5::Day5>),
+    Puzzle::new(6, "Chronal Coordinates", solve_timed::<day6::Day6>),
+    Puzzle::new(7, "The Sum of Its Parts", solve_timed::<day7::Day7>),
+    Puzzle::new(8, "Memory Maneuver", solve_timed::<day8::Day8>),
+    Puzzle::new(9, "Marble Mania", solve_timed::<day9::Day9>),
+    Puzzle::new(10, "The Stars Align", solve_timed::<day10::Day10>),
+    Puzzle::new(11, "Chronal Charge", solve_timed::<day11::Day11>),
+    Puzzle::new(
+        12,
+        "Subterranean Sustainability",
+        solve_timed::<day12::Day12>,
+    ),
+    Puzzle::new(13, "Mine Cart Madness", solve_timed::<day13::Day13>),
+    Puzzle::new(14, "Chocolate Charts", solve_timed::<day14::Day14>),
+    Puzzle::new(15, "Beverage Bandits", solve_timed::<day15::Day15>),
+    Puzzle::new(16, "Chronal Classification", solve_timed::<day16::Day16>),
+    Puzzle::new(17, "Reservoir Research", solve_timed::<day17::Day17>),
+    Puzzle::new(
+        18,
+        "Settlers of The North Pole",
+        solve_timed::<day18::Day18>,
+    ),
+    Puzzle::new(19, "Go With The Flow", solve_timed::<day19::Day19>),
+    Puzzle::new(20, "A Regular Map", solve_timed::<day20::Day20>),
+    Puzzle::new(21, "Chronal Conversion", solve_timed::<day21::Day21>),
+    Puzzle::new(22, "Mode Maze", solve_timed::<day22::Day22>),
+    Puzzle::new(
+        23,
+        "Experimental Emergency Teleportation",
+        solve_timed::<day23::Day23>,
+    ),
+    Puzzle::new(
+        24,
+        "Immune System Simulator 20XX",
+        solve_timed::<day24::Day24>,
+    ),
+    Puzzle::new(
+        25,
+        "Four-Dimensional Adventure",
+        solve_timed::<day25::Day25>,
+    ),
+];
@@ -0,0 +1,72 @@
+use crate::input::InputError;
+use std::fmt::Display;
+
+/// An error in the puzzle input, pointing at the offending line and column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The 1-based line number.
+    pub line: usize,
+    /// The 1-based column or `None` if the error concerns the line as a whole.
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new<S: Into<String>>(line: usize, column: Option<usize>, message: S) -> ParseError {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self.column {
+            Some(column) => write!(f, "line {}, column {}: {}", self.line, column, self.message),
+            None => write!(f, "line {}: {}", self.line, self.message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// An error that occurred while running a puzzle.
+#[derive(Debug)]
+pub enum Error {
+    /// The input could not be read.
+    Input(InputError),
+    /// The input could not be parsed. `input` names where the input was read from.
+    Parse {
+        day: u32,
+        input: String,
+        error: ParseError,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            Error::Input(error) => write!(f, "{}", error),
+            Error::Parse { day, input, error } => {
+                write!(f, "day{} {} {}", day, input, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Input(error) => Some(error),
+            Error::Parse { error, .. } => Some(error),
+        }
+    }
+}
+
+impl From<InputError> for Error {
+    fn from(error: InputError) -> Self {
+        Error::Input(error)
+    }
+}
@@ -0,0 +1,355 @@
+use crate::parse::lines;
+use crate::{Bounds, ParseError, Point};
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+/// The offsets of the 4 orthogonal neighbours in reading order.
+const NEIGHBOURS4: [(i32, i32); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// The offsets of all 8 neighbours, including the diagonal ones, in reading order.
+const NEIGHBOURS8: [(i32, i32); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular grid of cells stored row by row. Cells are addressed by `Point`; the top-left
+/// cell is at `origin`, which allows grids that do not start at `(0, 0)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    origin: Point,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with its top-left cell at `(0, 0)` with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::with_origin(Point::new(0, 0), width, height, value)
+    }
+
+    /// Creates a grid with its top-left cell at `origin` with every cell set to `value`.
+    pub fn with_origin(origin: Point, width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![value; width * height],
+            origin,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a grid covering `bounds` with every cell set to `value`.
+    pub fn from_bounds(bounds: Bounds, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::with_origin(bounds.min, bounds.width(), bounds.height(), value)
+    }
+
+    /// Creates a grid with its top-left cell at `(0, 0)` by calling `f` for every cell in reading
+    /// order.
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Grid<T>
+    where
+        F: FnMut(Point) -> T,
+    {
+        let mut grid = Grid {
+            cells: Vec::with_capacity(width * height),
+            origin: Point::new(0, 0),
+            width,
+            height,
+        };
+        for point in grid.points() {
+            grid.cells.push(f(point));
+        }
+        grid
+    }
+
+    /// Parses a character map with a line per row. `f` converts a character into a cell and
+    /// returns `None` for characters that are not allowed; `what` describes a cell in the error.
+    /// All rows must have the same length.
+    pub fn parse<F>(input: &str, what: &str, f: F) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(Point, char) -> Option<T>,
+    {
+        Grid::parse_map(input, None, what, f)
+    }
+
+    /// Like `parse` but allows rows to be shorter than the longest one, in which case they are
+    /// padded with `padding`. This is useful for maps with trailing whitespace stripped.
+    pub fn parse_padded<F>(
+        input: &str,
+        padding: char,
+        what: &str,
+        f: F,
+    ) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(Point, char) -> Option<T>,
+    {
+        Grid::parse_map(input, Some(padding), what, f)
+    }
+
+    fn parse_map<F>(
+        input: &str,
+        padding: Option<char>,
+        what: &str,
+        mut f: F,
+    ) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(Point, char) -> Option<T>,
+    {
+        let rows: Vec<_> = lines(input).collect();
+        let width = rows
+            .iter()
+            .map(|row| row.text.chars().count())
+            .max()
+            .unwrap_or(0);
+        if width == 0 {
+            return Err(ParseError::new(1, None, "expected a map"));
+        }
+
+        let mut cells = Vec::with_capacity(width * rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            let mut x = 0;
+            for (offset, c) in row.char_indices() {
+                let point = Point::new(x, y as i32);
+                match f(point, c) {
+                    Some(cell) => cells.push(cell),
+                    None => return Err(row.error_at(offset, format!("invalid {} `{}`", what, c))),
+                }
+                x += 1;
+            }
+            while (x as usize) < width {
+                let cell = padding.and_then(|c| f(Point::new(x, y as i32), c));
+                match cell {
+                    Some(cell) => cells.push(cell),
+                    None => {
+                        return Err(
+                            row.error_at(row.text.len(), format!("expected {} {}s", width, what))
+                        )
+                    }
+                }
+                x += 1;
+            }
+        }
+
+        Ok(Grid {
+            cells,
+            origin: Point::new(0, 0),
+            width,
+            height: rows.len(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the position of the top-left cell.
+    pub fn origin(&self) -> Point {
+        self.origin
+    }
+
+    /// Returns the position of the bottom-right cell.
+    pub fn max(&self) -> Point {
+        Point::new(
+            self.origin.x + self.width as i32 - 1,
+            self.origin.y + self.height as i32 - 1,
+        )
+    }
+
+    /// Returns the positions of the top-left and bottom-right cells.
+    pub fn bounds(&self) -> Bounds {
+        Bounds {
+            min: self.origin,
+            max: self.max(),
+        }
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        // Points left of or above the origin wrap around to large values
+        let x = point.x.wrapping_sub(self.origin.x) as u32 as usize;
+        let y = point.y.wrapping_sub(self.origin.y) as u32 as usize;
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    /// Returns the cell at `point` or `None` if it lies outside of the grid.
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        match self.index_of(point) {
+            Some(idx) => Some(&mut self.cells[idx]),
+            None => None,
+        }
+    }
+
+    /// Returns the positions of all cells in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (origin, width, height) = (self.origin, self.width as i32, self.height as i32);
+        (0..height)
+            .flat_map(move |y| (0..width).map(move |x| Point::new(origin.x + x, origin.y + y)))
+    }
+
+    /// Returns all cells together with their position in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// Returns all cells in reading order.
+    pub fn values(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Returns the cells of every row from top to bottom.
+    pub fn rows(&self) -> std::slice::Chunks<'_, T> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Returns the cells of the row at `y`.
+    pub fn row(&self, y: i32) -> &[T] {
+        let start = self
+            .index_of(Point::new(self.origin.x, y))
+            .expect("row out of bounds");
+        &self.cells[start..start + self.width]
+    }
+
+    /// Returns the cells of the column at `x` from top to bottom.
+    pub fn column(&self, x: i32) -> impl Iterator<Item = &T> {
+        let start = self
+            .index_of(Point::new(x, self.origin.y))
+            .expect("column out of bounds");
+        self.cells[start..].iter().step_by(self.width)
+    }
+
+    /// Returns the positions of the orthogonal neighbours of `point` that lie within the grid, in
+    /// reading order.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.offset_points(point, &NEIGHBOURS4)
+    }
+
+    /// Returns the positions of all neighbours of `point`, including the diagonal ones, that lie
+    /// within the grid, in reading order.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.offset_points(point, &NEIGHBOURS8)
+    }
+
+    fn offset_points<'a>(
+        &'a self,
+        point: Point,
+        offsets: &'static [(i32, i32)],
+    ) -> impl Iterator<Item = Point> + 'a {
+        offsets
+            .iter()
+            .map(move |(dx, dy)| Point::new(point.x + dx, point.y + dy))
+            .filter(move |p| self.contains(*p))
+    }
+
+    /// Returns a grid of the same size and origin with `f` applied to every cell.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            origin: self.origin,
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        match self.index_of(point) {
+            Some(idx) => &self.cells[idx],
+            None => panic!("{:?} lies outside of the grid", point),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        match self.index_of(point) {
+            Some(idx) => &mut self.cells[idx],
+            None => panic!("{:?} lies outside of the grid", point),
+        }
+    }
+}
+
+/// Renders the grid with a line per row.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_and_render() {
+        let grid = Grid::parse("#.#\n..#", "tile", |_, c| match c {
+            '#' | '.' => Some(c),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], '#');
+        assert_eq!(grid.column(2).collect::<String>(), "##");
+        assert_eq!(grid.to_string(), "#.#\n..#\n");
+
+        let error = Grid::parse("#.\n#", "tile", |_, c| Some(c)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, Some(2)));
+        let error = Grid::parse("#x", "tile", |_, c| Some(c).filter(|c| *c == '#')).unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 2: invalid tile `x`");
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::with_origin(Point::new(5, 5), 3, 3, 0);
+        assert_eq!(grid.neighbours8(Point::new(6, 6)).count(), 8);
+        assert_eq!(
+            grid.neighbours4(Point::new(5, 5)).collect::<Vec<_>>(),
+            vec![Point::new(6, 5), Point::new(5, 6)]
+        );
+        assert_eq!(grid.get(Point::new(4, 5)), None);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{Error, ParseError};
+use std::fmt::Display;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// The environment variable that overrides the directory containing the `dayN/input` files.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where to read the puzzle input of a day from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// The `dayN/input` file in the input directory, see `input_dir`.
+    Default,
+    /// A specific file.
+    Path(PathBuf),
+    /// The standard input of the process.
+    Stdin,
+}
+
+impl InputSource {
+    /// Interprets a command line argument as an input source: no argument selects the default
+    /// input file, `-` selects stdin and anything else is a path.
+    pub fn from_arg(arg: Option<&str>) -> InputSource {
+        match arg {
+            None => InputSource::Default,
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::Path(PathBuf::from(path)),
+        }
+    }
+
+    /// Returns the name of the input to use in messages.
+    pub fn name(&self, day: u32) -> String {
+        match self {
+            InputSource::Default => default_path(day).display().to_string(),
+            InputSource::Path(path) => path.display().to_string(),
+            InputSource::Stdin => "stdin".to_owned(),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            InputSource::Default => write!(f, "default input"),
+            InputSource::Path(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/// An error that occurred while reading the input of a day.
+#[derive(Debug)]
+pub struct InputError {
+    pub day: u32,
+    /// The file that was read or `None` when reading from stdin.
+    pub path: Option<PathBuf>,
+    pub error: std::io::Error,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match &self.path {
+            Some(path) => write!(
+                f,
+                "Could not read input of day {} from `{}`: {}",
+                self.day,
+                path.display(),
+                self.error
+            ),
+            None => write!(
+                f,
+                "Could not read input of day {} from stdin: {}",
+                self.day, self.error
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Returns the directory that contains the `dayN/input` files. This is the value of the
+/// `AOC_INPUT_DIR` environment variable if set, otherwise the `inputs` directory of the
+/// repository so the binaries work regardless of the working directory.
+pub fn input_dir() -> PathBuf {
+    std::env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs"))
+}
+
+/// Returns the path of the default input file of a day.
+pub fn default_path(day: u32) -> PathBuf {
+    input_dir().join(format!("day{}", day)).join("input")
+}
+
+/// Reads the input of a day from the given source.
+pub fn load(day: u32, source: &InputSource) -> Result<String, InputError> {
+    let path = match source {
+        InputSource::Default => default_path(day),
+        InputSource::Path(path) => path.clone(),
+        InputSource::Stdin => {
+            let mut input = String::new();
+            return std::io::stdin()
+                .read_to_string(&mut input)
+                .map(|_| input)
+                .map_err(|error| InputError {
+                    day,
+                    path: None,
+                    error,
+                });
+        }
+    };
+
+    std::fs::read_to_string(&path).map_err(|error| InputError {
+        day,
+        path: Some(path),
+        error,
+    })
+}
+
+/// Reads and parses the input of a day from the source passed as the first command line
+/// argument. Prints the error and exits the process if the input cannot be read or parsed.
+pub fn from_args<T>(day: u32, parse: fn(&str) -> Result<T, ParseError>) -> T {
+    let arg = std::env::args().nth(1);
+    let source = InputSource::from_arg(arg.as_deref());
+    let result = load(day, &source).map_err(Error::from).and_then(|input| {
+        parse(&input).map_err(|error| Error::Parse {
+            day,
+            input: source.name(day),
+            error,
+        })
+    });
+    result.unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    })
+}
@@ -1,3 +1,28 @@
+pub mod answers;
+pub mod battle;
+pub mod bench;
+pub mod cycle;
+pub mod days;
+mod error;
+mod grid;
+pub mod input;
+pub mod parse;
 mod point;
+mod point3;
+pub mod search;
+mod solution;
+mod union_find;
+pub mod vm;
 
-pub use self::point::Point;
+pub use self::days::PUZZLES;
+pub use self::error::{Error, ParseError};
+pub use self::grid::Grid;
+pub use self::point::{Bounds, Direction, ParsePointError, Point};
+pub use self::point3::{ParsePoint3Error, Point3};
+pub use self::solution::{solve, Answers, Puzzle, Solution, Timings};
+pub use self::union_find::UnionFind;
+
+/// Returns the puzzle for the given day, if it has been solved.
+pub fn puzzle(day: u32) -> Option<&'static Puzzle> {
+    PUZZLES.iter().find(|p| p.day == day)
+}
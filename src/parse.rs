@@ -0,0 +1,174 @@
+use crate::ParseError;
+use regex::Regex;
+use std::fmt::Display;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// A piece of the puzzle input that remembers where it came from, so parse errors can point at
+/// the exact line and column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+    /// The 1-based line number.
+    pub line: usize,
+    /// The 1-based column of the first character.
+    pub column: usize,
+    pub text: &'a str,
+}
+
+/// Returns all lines of the input.
+pub fn lines(input: &str) -> impl Iterator<Item = Span<'_>> {
+    input.lines().enumerate().map(|(idx, text)| Span {
+        line: idx + 1,
+        column: 1,
+        text,
+    })
+}
+
+/// Returns the line at which parsing stopped if the input ended prematurely.
+pub fn end_of_input(input: &str) -> usize {
+    input.lines().count() + 1
+}
+
+impl<'a> Span<'a> {
+    pub fn new(line: usize, column: usize, text: &'a str) -> Span<'a> {
+        Span { line, column, text }
+    }
+
+    /// Returns an error pointing at the start of the span.
+    pub fn error<S: Into<String>>(&self, message: S) -> ParseError {
+        ParseError::new(self.line, Some(self.column), message)
+    }
+
+    /// Returns an error pointing at the character at the given byte offset in the span.
+    pub fn error_at<S: Into<String>>(&self, offset: usize, message: S) -> ParseError {
+        self.slice(offset..offset).error(message)
+    }
+
+    /// Returns the part of the span within the given byte range.
+    pub fn slice(&self, range: Range<usize>) -> Span<'a> {
+        Span {
+            line: self.line,
+            column: self.column + self.text[..range.start].chars().count(),
+            text: &self.text[range],
+        }
+    }
+
+    /// Returns the span of `part`, which must be a substring of this span.
+    fn sub(&self, part: &'a str) -> Span<'a> {
+        let offset = part.as_ptr() as usize - self.text.as_ptr() as usize;
+        self.slice(offset..offset + part.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn trim(&self) -> Span<'a> {
+        self.sub(self.text.trim())
+    }
+
+    pub fn split(self, separator: &'a str) -> impl Iterator<Item = Span<'a>> {
+        self.text.split(separator).map(move |part| self.sub(part))
+    }
+
+    pub fn split_whitespace(self) -> impl Iterator<Item = Span<'a>> {
+        self.text.split_whitespace().map(move |part| self.sub(part))
+    }
+
+    /// Returns the characters of the span together with their byte offset.
+    pub fn char_indices(&self) -> std::str::CharIndices<'a> {
+        self.text.char_indices()
+    }
+
+    /// Parses the text of the span.
+    pub fn parse<T>(&self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text
+            .parse()
+            .map_err(|e| self.error(format!("invalid value `{}`: {}", self.text, e)))
+    }
+
+    /// Returns the rest of the span after `prefix`.
+    pub fn strip_prefix(&self, prefix: &str) -> Result<Span<'a>, ParseError> {
+        match self.text.strip_prefix(prefix) {
+            Some(rest) => Ok(self.sub(rest)),
+            None => Err(self.error(format!("expected `{}`", prefix))),
+        }
+    }
+
+    /// Returns the span without `suffix`.
+    pub fn strip_suffix(&self, suffix: &str) -> Result<Span<'a>, ParseError> {
+        match self.text.strip_suffix(suffix) {
+            Some(rest) => Ok(self.sub(rest)),
+            None => Err(self.error_at(self.text.len(), format!("expected `{}`", suffix))),
+        }
+    }
+
+    /// Matches the regex against the span. `expected` describes the expected format in the error
+    /// if the regex does not match.
+    pub fn captures(&self, regex: &Regex, expected: &str) -> Result<Captures<'a>, ParseError> {
+        match regex.captures(self.text) {
+            Some(captures) => Ok(Captures {
+                span: *self,
+                captures,
+            }),
+            None => Err(self.error(format!("expected `{}`", expected))),
+        }
+    }
+}
+
+/// The capture groups of a regex matched against a `Span`.
+pub struct Captures<'a> {
+    span: Span<'a>,
+    captures: regex::Captures<'a>,
+}
+
+impl<'a> Captures<'a> {
+    /// Returns the span of a capture group if it participated in the match.
+    pub fn get(&self, group: usize) -> Option<Span<'a>> {
+        self.captures
+            .get(group)
+            .map(|m| self.span.slice(m.start()..m.end()))
+    }
+
+    /// Returns the text of a capture group or an empty string if it did not participate in the
+    /// match.
+    pub fn str(&self, group: usize) -> &'a str {
+        self.captures.get(group).map_or("", |m| m.as_str())
+    }
+
+    /// Parses the text of a capture group.
+    pub fn parse<T>(&self, group: usize) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match self.get(group) {
+            Some(span) => span.parse(),
+            None => Err(self.span.error(format!("missing capture group {}", group))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn error_positions() {
+        let input = "1 2\n3 x 5";
+        let values: Result<Vec<u32>, ParseError> = lines(input)
+            .flat_map(|l| l.split_whitespace())
+            .map(|v| v.parse())
+            .collect();
+        let error = values.unwrap_err();
+        assert_eq!((error.line, error.column), (2, Some(3)));
+
+        let line = lines("Before: [1, 2]").next().unwrap();
+        let error = line.strip_prefix("After:").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 1: expected `After:`");
+    }
+}
@@ -1,4 +1,12 @@
-#[derive(Debug, Clone, Copy)]
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// A position or offset on a 2D grid where `y` grows downwards, as in all the puzzle maps.
+///
+/// Points are ordered in reading order: top to bottom, then left to right.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct Point {
     pub x: i32,
     pub y: i32,
@@ -11,15 +19,15 @@ impl Point {
 
     pub fn max_value() -> Point {
         Point {
-            x: std::i32::MAX,
-            y: std::i32::MAX,
+            x: i32::MAX,
+            y: i32::MAX,
         }
     }
 
     pub fn min_value() -> Point {
         Point {
-            x: std::i32::MIN,
-            y: std::i32::MIN,
+            x: i32::MIN,
+            y: i32::MIN,
         }
     }
 
@@ -36,4 +44,304 @@ impl Point {
             y: self.y.max(other.y),
         }
     }
+
+    /// Returns the sum of the absolute coordinates.
+    pub fn manhattan_length(self) -> i32 {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn manhattan_distance(self, other: Point) -> i32 {
+        (self - other).manhattan_length()
+    }
+
+    /// Returns the point one step in the given direction.
+    pub fn step(self, direction: Direction) -> Point {
+        self + direction.offset()
+    }
+
+    /// Returns the 4 orthogonal neighbours in reading order.
+    pub fn neighbours(self) -> [Point; 4] {
+        [
+            self.step(Direction::Up),
+            self.step(Direction::Left),
+            self.step(Direction::Right),
+            self.step(Direction::Down),
+        ]
+    }
+
+    pub fn is_adjacent_to(self, other: Point) -> bool {
+        self.manhattan_distance(other) == 1
+    }
+
+    /// Rotates the point a quarter turn clockwise around the origin.
+    pub fn rotate_right(self) -> Point {
+        Point::new(-self.y, self.x)
+    }
+
+    /// Rotates the point a quarter turn counterclockwise around the origin.
+    pub fn rotate_left(self) -> Point {
+        Point::new(self.y, -self.x)
+    }
+}
+
+impl Ord for Point {
+    fn cmp(&self, other: &Point) -> Ordering {
+        self.y.cmp(&other.y).then(self.x.cmp(&other.x))
+    }
+}
+
+impl PartialOrd for Point {
+    fn partial_cmp(&self, other: &Point) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: i32) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// The error returned when parsing a `Point` fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePointError;
+
+impl Display for ParsePointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "expected `x, y`")
+    }
+}
+
+impl std::error::Error for ParsePointError {}
+
+/// Parses a point written as `x, y`. The space after the comma is optional.
+impl FromStr for Point {
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Point, ParsePointError> {
+        let mut coords = s.split(',').map(|c| c.trim().parse::<i32>());
+        match (coords.next(), coords.next(), coords.next()) {
+            (Some(Ok(x)), Some(Ok(y)), None) => Ok(Point::new(x, y)),
+            _ => Err(ParsePointError),
+        }
+    }
+}
+
+/// One of the 4 directions on a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// All directions in the reading order of the points they point to.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Left,
+        Direction::Right,
+        Direction::Down,
+    ];
+
+    /// Returns the offset of a single step in this direction.
+    pub fn offset(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+            Direction::Right => Point::new(1, 0),
+        }
+    }
+
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn reverse(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+/// An axis-aligned rectangle given by its top-left and bottom-right corners, both inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    /// Returns the bounds of a single point.
+    pub fn new(point: Point) -> Bounds {
+        Bounds {
+            min: point,
+            max: point,
+        }
+    }
+
+    /// Returns the smallest bounds containing all points, or `None` if there are no points.
+    pub fn from_points<I>(points: I) -> Option<Bounds>
+    where
+        I: IntoIterator<Item = Point>,
+    {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Bounds::new(first), Bounds::extend))
+    }
+
+    /// Returns the bounds grown to include `point`.
+    pub fn extend(self, point: Point) -> Bounds {
+        Bounds {
+            min: self.min.min(&point),
+            max: self.max.max(&point),
+        }
+    }
+
+    /// Returns the bounds grown by `amount` on every side.
+    pub fn grow(self, amount: i32) -> Bounds {
+        Bounds {
+            min: self.min - Point::new(amount, amount),
+            max: self.max + Point::new(amount, amount),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1) as usize
+    }
+
+    /// Returns the number of points within the bounds.
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= self.min.x
+            && point.x <= self.max.x
+            && point.y >= self.min.y
+            && point.y <= self.max.y
+    }
+
+    /// Returns whether `point` lies on the edge of the bounds.
+    pub fn on_edge(&self, point: Point) -> bool {
+        self.contains(point)
+            && (point.x == self.min.x
+                || point.x == self.max.x
+                || point.y == self.min.y
+                || point.y == self.max.y)
+    }
+
+    /// Returns all points within the bounds in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (min, max) = (self.min, self.max);
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reading_order() {
+        let mut points = vec![Point::new(1, 1), Point::new(2, 0), Point::new(0, 1)];
+        points.sort();
+        assert_eq!(
+            points,
+            vec![Point::new(2, 0), Point::new(0, 1), Point::new(1, 1)]
+        );
+        assert_eq!(
+            Direction::ALL
+                .iter()
+                .map(|d| Point::new(0, 0).step(*d))
+                .collect::<Vec<_>>(),
+            Point::new(0, 0).neighbours().to_vec()
+        );
+    }
+
+    #[test]
+    fn rotation() {
+        let p = Point::new(2, -1);
+        assert_eq!(p.rotate_right().rotate_left(), p);
+        assert_eq!(p.rotate_right().rotate_right(), -p);
+        assert_eq!(
+            Direction::Up.offset().rotate_right(),
+            Direction::Up.turn_right().offset()
+        );
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("3, -4".parse(), Ok(Point::new(3, -4)));
+        assert_eq!("3,4".parse(), Ok(Point::new(3, 4)));
+        assert_eq!("3".parse::<Point>(), Err(ParsePointError));
+        assert_eq!("3, 4, 5".parse::<Point>(), Err(ParsePointError));
+    }
 }
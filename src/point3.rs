@@ -0,0 +1,133 @@
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// A position or offset in 3D space. The coordinates are 64 bits wide since the puzzles using
+/// them have coordinates in the hundreds of millions, which easily overflow when added up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub fn new(x: i64, y: i64, z: i64) -> Point3 {
+        Point3 { x, y, z }
+    }
+
+    pub fn min(self, other: &Point3) -> Point3 {
+        Point3 {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y),
+            z: self.z.min(other.z),
+        }
+    }
+
+    pub fn max(self, other: &Point3) -> Point3 {
+        Point3 {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+            z: self.z.max(other.z),
+        }
+    }
+
+    /// Returns the sum of the absolute coordinates.
+    pub fn manhattan_length(self) -> i64 {
+        self.x.abs() + self.y.abs() + self.z.abs()
+    }
+
+    pub fn manhattan_distance(self, other: Point3) -> i64 {
+        (self - other).manhattan_length()
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Point3 {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, other: Point3) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, other: Point3) -> Point3 {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl SubAssign for Point3 {
+    fn sub_assign(&mut self, other: Point3) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Point3;
+
+    fn neg(self) -> Point3 {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<i64> for Point3 {
+    type Output = Point3;
+
+    fn mul(self, factor: i64) -> Point3 {
+        Point3::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+impl Display for Point3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// The error returned when parsing a `Point3` fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePoint3Error;
+
+impl Display for ParsePoint3Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "expected `x, y, z`")
+    }
+}
+
+impl std::error::Error for ParsePoint3Error {}
+
+/// Parses a point written as `x, y, z`. The spaces after the commas are optional.
+impl FromStr for Point3 {
+    type Err = ParsePoint3Error;
+
+    fn from_str(s: &str) -> Result<Point3, ParsePoint3Error> {
+        let mut coords = s.split(',').map(|c| c.trim().parse::<i64>());
+        match (coords.next(), coords.next(), coords.next(), coords.next()) {
+            (Some(Ok(x)), Some(Ok(y)), Some(Ok(z)), None) => Ok(Point3::new(x, y, z)),
+            _ => Err(ParsePoint3Error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!("1, -2, 3".parse(), Ok(Point3::new(1, -2, 3)));
+        assert_eq!("1,2".parse::<Point3>(), Err(ParsePoint3Error));
+        assert_eq!(
+            Point3::new(1, -2, 3).manhattan_distance(Point3::new(0, 0, 0)),
+            6
+        );
+    }
+}
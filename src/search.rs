@@ -0,0 +1,535 @@
+//! Graph searches over graphs described by a neighbour closure. Nodes must be `Ord` so that ties
+//! are always broken the same way; for `Point` this is reading order. `GridBfs` is a faster
+//! breadth-first search for the cells of a `Grid` that is repeated many times.
+
+use crate::{Grid, Point};
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// How a node was reached by a search.
+#[derive(Debug, Clone, Copy)]
+struct Visit<N, C> {
+    cost: C,
+    parent: Option<N>,
+    /// The index of the start node the node is closest to, or `None` if it is equally close to
+    /// multiple start nodes.
+    source: Option<usize>,
+}
+
+/// The shortest paths from one or more start nodes to every node reached by a search.
+#[derive(Debug, Clone)]
+pub struct Paths<N, C = usize> {
+    visits: HashMap<N, Visit<N, C>>,
+}
+
+/// A path through a graph, including both the start and the end node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C = usize> {
+    pub cost: C,
+    pub nodes: Vec<N>,
+}
+
+impl<N, C> Paths<N, C>
+where
+    N: Copy + Eq + Hash + Ord,
+    C: Copy + Ord,
+{
+    /// Returns the number of reached nodes.
+    pub fn len(&self) -> usize {
+        self.visits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.visits.is_empty()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.visits.contains_key(node)
+    }
+
+    /// Returns the cost of the shortest path to `node`, or `None` if it was not reached.
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.visits.get(node).map(|v| v.cost)
+    }
+
+    /// Returns the index of the start node that is closest to `node`, or `None` if `node` was not
+    /// reached or is equally close to multiple start nodes.
+    pub fn source(&self, node: &N) -> Option<usize> {
+        self.visits.get(node).and_then(|v| v.source)
+    }
+
+    /// Returns all reached nodes with the cost of their shortest path, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&N, C)> {
+        self.visits.iter().map(|(node, visit)| (node, visit.cost))
+    }
+
+    /// Returns the closest reached node for which `f` returns true. Ties are broken by the order
+    /// of the nodes.
+    pub fn nearest<F>(&self, mut f: F) -> Option<N>
+    where
+        F: FnMut(&N) -> bool,
+    {
+        self.visits
+            .iter()
+            .filter(|(node, _)| f(node))
+            .min_by_key(|(node, visit)| (visit.cost, **node))
+            .map(|(node, _)| *node)
+    }
+
+    /// Returns the shortest path from a start node to `node`.
+    pub fn path_to(&self, node: N) -> Option<Path<N, C>> {
+        let cost = self.visits.get(&node)?.cost;
+        let mut nodes = vec![node];
+        let mut current = node;
+        while let Some(parent) = self.visits[&current].parent {
+            nodes.push(parent);
+            current = parent;
+        }
+        nodes.reverse();
+        Some(Path { cost, nodes })
+    }
+}
+
+/// Finds the shortest paths from `start` to all reachable nodes in an unweighted graph.
+///
+/// The nodes at each distance are expanded in order, so among multiple shortest paths to a node
+/// the one through the smallest nodes closest to the end is recorded.
+pub fn bfs<N, F, I>(start: N, neighbours: F) -> Paths<N>
+where
+    N: Copy + Eq + Hash + Ord,
+    F: FnMut(N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    bfs_multi(Some(start), neighbours)
+}
+
+/// Like `bfs` but starts from multiple nodes at once. Every node records which of the start nodes
+/// it is closest to, see `Paths::source`.
+pub fn bfs_multi<N, S, F, I>(starts: S, mut neighbours: F) -> Paths<N>
+where
+    N: Copy + Eq + Hash + Ord,
+    S: IntoIterator<Item = N>,
+    F: FnMut(N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut visits = HashMap::new();
+    let mut layer = Vec::new();
+    for (idx, start) in starts.into_iter().enumerate() {
+        let visit = Visit {
+            cost: 0,
+            parent: None,
+            source: Some(idx),
+        };
+        // A node listed twice is closest to the first occurrence
+        if visits.insert(start, visit).is_none() {
+            layer.push(start);
+        }
+    }
+
+    let mut distance = 0;
+    while !layer.is_empty() {
+        layer.sort();
+        let mut next_layer = Vec::new();
+        for node in layer {
+            let source = visits[&node].source;
+            for neighbour in neighbours(node) {
+                match visits.get_mut(&neighbour) {
+                    None => {
+                        visits.insert(
+                            neighbour,
+                            Visit {
+                                cost: distance + 1,
+                                parent: Some(node),
+                                source,
+                            },
+                        );
+                        next_layer.push(neighbour);
+                    }
+                    Some(visit) if visit.cost == distance + 1 && visit.source != source => {
+                        visit.source = None;
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        layer = next_layer;
+        distance += 1;
+    }
+
+    Paths { visits }
+}
+
+/// Finds the closest node from `start` for which `is_target` returns true in an unweighted graph.
+/// Stops as soon as a target is found. Ties are broken by the order of the nodes.
+pub fn bfs_nearest<N, F, I, T>(start: N, mut neighbours: F, mut is_target: T) -> Option<Path<N>>
+where
+    N: Copy + Eq + Hash + Ord,
+    F: FnMut(N) -> I,
+    I: IntoIterator<Item = N>,
+    T: FnMut(&N) -> bool,
+{
+    let mut paths = Paths {
+        visits: HashMap::new(),
+    };
+    paths.visits.insert(
+        start,
+        Visit {
+            cost: 0,
+            parent: None,
+            source: Some(0),
+        },
+    );
+
+    let mut layer = vec![start];
+    let mut distance = 0;
+    while !layer.is_empty() {
+        layer.sort();
+        if let Some(target) = layer.iter().find(|n| is_target(n)) {
+            return paths.path_to(*target);
+        }
+
+        let mut next_layer = Vec::new();
+        for node in layer {
+            for neighbour in neighbours(node) {
+                if let Entry::Vacant(entry) = paths.visits.entry(neighbour) {
+                    entry.insert(Visit {
+                        cost: distance + 1,
+                        parent: Some(node),
+                        source: Some(0),
+                    });
+                    next_layer.push(neighbour);
+                }
+            }
+        }
+        layer = next_layer;
+        distance += 1;
+    }
+    None
+}
+
+/// Finds the cheapest paths from `start` to all reachable nodes. `neighbours` returns the
+/// neighbours of a node together with the cost of moving there, which must not be negative.
+pub fn dijkstra<N, C, F, I>(start: N, mut neighbours: F) -> Paths<N, C>
+where
+    N: Copy + Eq + Hash + Ord,
+    C: Copy + Ord + Add<Output = C> + Default,
+    F: FnMut(N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths {
+        visits: HashMap::new(),
+    };
+    search(
+        start,
+        &mut neighbours,
+        |_| C::default(),
+        |_| false,
+        &mut paths,
+    );
+    paths
+}
+
+/// Finds the cheapest path from `start` to a node for which `is_goal` returns true.
+pub fn shortest_path<N, C, F, I, G>(start: N, mut neighbours: F, is_goal: G) -> Option<Path<N, C>>
+where
+    N: Copy + Eq + Hash + Ord,
+    C: Copy + Ord + Add<Output = C> + Default,
+    F: FnMut(N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    G: FnMut(&N) -> bool,
+{
+    a_star(start, &mut neighbours, |_| C::default(), is_goal)
+}
+
+/// Finds the cheapest path from `start` to a node for which `is_goal` returns true, guided by
+/// `heuristic`. The heuristic must never overestimate the remaining cost to a goal, otherwise the
+/// returned path may not be the cheapest.
+pub fn a_star<N, C, F, I, H, G>(
+    start: N,
+    mut neighbours: F,
+    heuristic: H,
+    is_goal: G,
+) -> Option<Path<N, C>>
+where
+    N: Copy + Eq + Hash + Ord,
+    C: Copy + Ord + Add<Output = C> + Default,
+    F: FnMut(N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+    G: FnMut(&N) -> bool,
+{
+    let mut paths = Paths {
+        visits: HashMap::new(),
+    };
+    let goal = search(start, &mut neighbours, heuristic, is_goal, &mut paths)?;
+    paths.path_to(goal)
+}
+
+/// The best-first search behind `dijkstra` and `a_star`. Returns the first goal that is reached.
+fn search<N, C, F, I, H, G>(
+    start: N,
+    neighbours: &mut F,
+    mut heuristic: H,
+    mut is_goal: G,
+    paths: &mut Paths<N, C>,
+) -> Option<N>
+where
+    N: Copy + Eq + Hash + Ord,
+    C: Copy + Ord + Add<Output = C> + Default,
+    F: FnMut(N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+    G: FnMut(&N) -> bool,
+{
+    let zero = C::default();
+    paths.visits.insert(
+        start,
+        Visit {
+            cost: zero,
+            parent: None,
+            source: Some(0),
+        },
+    );
+
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((heuristic(&start), zero, start)));
+    while let Some(Reverse((_, cost, node))) = queue.pop() {
+        if cost > paths.visits[&node].cost {
+            // There is a cheaper way to this node which has already been expanded
+            continue;
+        }
+        if is_goal(&node) {
+            return Some(node);
+        }
+
+        for (neighbour, step) in neighbours(node) {
+            let new_cost = cost + step;
+            let better = paths
+                .visits
+                .get(&neighbour)
+                .map_or(true, |visit| new_cost < visit.cost);
+            if better {
+                paths.visits.insert(
+                    neighbour,
+                    Visit {
+                        cost: new_cost,
+                        parent: Some(node),
+                        source: Some(0),
+                    },
+                );
+                queue.push(Reverse((
+                    new_cost + heuristic(&neighbour),
+                    new_cost,
+                    neighbour,
+                )));
+            }
+        }
+    }
+    None
+}
+
+/// How a cell was reached by a `GridBfs`.
+#[derive(Debug, Clone, Copy, Default)]
+struct GridVisit {
+    /// The number of the search that last reached the cell.
+    search: u32,
+    distance: usize,
+    source: usize,
+}
+
+/// A breadth-first search over the cells of a grid that keeps its buffers between searches, which
+/// makes it much faster than `bfs_multi` when the same grid is searched over and over.
+#[derive(Debug, Clone)]
+pub struct GridBfs {
+    visits: Grid<GridVisit>,
+    /// The number of the last search. Cells with an older number were not reached by it, so the
+    /// buffers never need to be cleared.
+    search: u32,
+    queue: VecDeque<Point>,
+}
+
+impl GridBfs {
+    /// Creates a search over the cells of grids with the same bounds as `grid`.
+    pub fn new<T>(grid: &Grid<T>) -> GridBfs {
+        GridBfs {
+            visits: Grid::from_bounds(grid.bounds(), GridVisit::default()),
+            search: 0,
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Searches from all `starts` at once through the cells for which `open` holds, replacing the
+    /// results of the previous search. Every reached cell records the first of the closest starts,
+    /// see `source`.
+    ///
+    /// Stops once all cells as close as the first reached cell for which `is_goal` holds are
+    /// reached from every closer cell, so their distances and sources are final.
+    pub fn search<O, G>(&mut self, starts: &[Point], mut open: O, mut is_goal: G)
+    where
+        O: FnMut(Point) -> bool,
+        G: FnMut(Point) -> bool,
+    {
+        if self.search == u32::MAX {
+            for visit in self.visits.values_mut() {
+                visit.search = 0;
+            }
+            self.search = 0;
+        }
+        self.search += 1;
+        let search = self.search;
+        self.queue.clear();
+
+        let mut goal_distance = None;
+        for (source, &start) in starts.iter().enumerate() {
+            match self.visits.get_mut(start) {
+                Some(visit) if visit.search != search => {
+                    *visit = GridVisit {
+                        search,
+                        distance: 0,
+                        source,
+                    };
+                    self.queue.push_back(start);
+                    if goal_distance.is_none() && is_goal(start) {
+                        goal_distance = Some(0);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        while let Some(point) = self.queue.pop_front() {
+            let GridVisit {
+                distance, source, ..
+            } = self.visits[point];
+            if goal_distance.is_some_and(|goal| distance >= goal) {
+                break;
+            }
+            for &neighbour in point.neighbours().iter() {
+                if !open(neighbour) {
+                    continue;
+                }
+                match self.visits.get_mut(neighbour) {
+                    Some(visit) if visit.search != search => {
+                        *visit = GridVisit {
+                            search,
+                            distance: distance + 1,
+                            source,
+                        };
+                        self.queue.push_back(neighbour);
+                        if goal_distance.is_none() && is_goal(neighbour) {
+                            goal_distance = Some(distance + 1);
+                        }
+                    }
+                    Some(visit) if visit.distance == distance + 1 => {
+                        visit.source = visit.source.min(source);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn visit(&self, point: Point) -> Option<&GridVisit> {
+        self.visits
+            .get(point)
+            .filter(|visit| self.search > 0 && visit.search == self.search)
+    }
+
+    /// Returns the distance from the closest start to `point`, or `None` if the last search did
+    /// not reach it.
+    pub fn distance(&self, point: Point) -> Option<usize> {
+        self.visit(point).map(|visit| visit.distance)
+    }
+
+    /// Returns the index of the first of the starts that are closest to `point`, or `None` if the
+    /// last search did not reach it.
+    pub fn source(&self, point: Point) -> Option<usize> {
+        self.visit(point).map(|visit| visit.source)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn open_neighbours(grid: &Grid<char>) -> impl FnMut(Point) -> Vec<Point> + '_ {
+        move |p| grid.neighbours4(p).filter(|n| grid[*n] == '.').collect()
+    }
+
+    #[test]
+    fn bfs_paths() {
+        let grid = Grid::parse(".....\n.###.\n.....", "tile", |_, c| Some(c)).unwrap();
+        let paths = bfs(Point::new(0, 1), open_neighbours(&grid));
+        assert_eq!(paths.distance(&Point::new(4, 1)), Some(6));
+        assert_eq!(paths.distance(&Point::new(2, 1)), None);
+
+        // Both routes around the wall are equally long, the path through the top row wins
+        let path = paths.path_to(Point::new(4, 1)).unwrap();
+        assert_eq!(path.nodes[1], Point::new(0, 0));
+
+        let nearest = bfs_nearest(Point::new(2, 0), open_neighbours(&grid), |p| p.x == 0);
+        assert_eq!(
+            nearest.map(|p| p.nodes.last().copied()),
+            Some(Some(Point::new(0, 0)))
+        );
+    }
+
+    #[test]
+    fn multi_source() {
+        let grid = Grid::new(5, 1, '.');
+        let paths = bfs_multi(
+            vec![Point::new(0, 0), Point::new(4, 0)],
+            open_neighbours(&grid),
+        );
+        let sources: Vec<Option<usize>> = grid.points().map(|p| paths.source(&p)).collect();
+        assert_eq!(sources, vec![Some(0), Some(0), None, Some(1), Some(1)]);
+    }
+
+    #[test]
+    fn grid_bfs() {
+        let grid = Grid::parse(".....\n.###.\n.....", "tile", |_, c| Some(c)).unwrap();
+        let mut search = GridBfs::new(&grid);
+        assert_eq!(search.distance(Point::new(0, 0)), None);
+
+        let starts = [Point::new(0, 1), Point::new(4, 1)];
+        search.search(&starts, |p| grid.get(p) == Some(&'.'), |_| false);
+        // The top middle cell is as close to both starts, the first of them wins
+        let sources: String = grid
+            .points()
+            .map(|p| search.source(p).map_or('#', |s| (b'0' + s as u8) as char))
+            .collect();
+        assert_eq!(sources, "000110###100011");
+        assert_eq!(search.distance(Point::new(2, 2)), Some(3));
+
+        // Stops after the cells as close as the goal, forgetting the previous search
+        search.search(&starts[..1], |p| grid.get(p) == Some(&'.'), |p| p.x == 1);
+        assert_eq!(search.distance(Point::new(1, 0)), Some(2));
+        assert_eq!(search.distance(Point::new(2, 0)), None);
+        assert_eq!(search.distance(Point::new(4, 1)), None);
+    }
+
+    #[test]
+    fn weighted() {
+        // The direct edge is more expensive than the detour
+        let edges = |n: u32| match n {
+            0 => vec![(1, 1), (2, 10)],
+            1 => vec![(2, 2)],
+            _ => vec![],
+        };
+        let path = shortest_path(0, edges, |n| *n == 2).unwrap();
+        assert_eq!(
+            path,
+            Path {
+                cost: 3,
+                nodes: vec![0, 1, 2]
+            }
+        );
+        assert_eq!(dijkstra(0, edges).distance(&2), Some(3));
+
+        let path = a_star(0, edges, |n| 2 - (*n).min(2), |n| *n == 2).unwrap();
+        assert_eq!(path.cost, 3);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::input::{self, InputSource};
+use crate::{Error, ParseError};
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+/// A solution to a single day of the Advent of Code calendar. The puzzle input is parsed once
+/// and then shared by both parts.
+pub trait Solution {
+    /// The parsed representation of the puzzle input.
+    type Input;
+
+    /// The answer to the first part of the puzzle.
+    type Part1: Display;
+
+    /// The answer to the second part of the puzzle.
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Self::Part1;
+
+    fn part2(input: &Self::Input) -> Self::Part2;
+}
+
+/// The formatted answers to both parts of a puzzle.
+#[derive(Debug, Clone)]
+pub struct Answers {
+    pub part1: String,
+    pub part2: String,
+}
+
+/// The time spent on each step of solving a puzzle.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+/// Parses the input and solves both parts of the puzzle with the `Solution` `S`.
+pub fn solve<S: Solution>(input: &str) -> Result<Answers, ParseError> {
+    solve_timed::<S>(input).map(|(answers, _)| answers)
+}
+
+/// Like `solve` but also measures how long parsing and each of the parts took. Formatting an
+/// answer is counted as part of solving it.
+pub fn solve_timed<S: Solution>(input: &str) -> Result<(Answers, Timings), ParseError> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parsed = Instant::now();
+    let part1 = S::part1(&input).to_string();
+    let solved1 = Instant::now();
+    let part2 = S::part2(&input).to_string();
+    let solved2 = Instant::now();
+
+    let timings = Timings {
+        parse: parsed - start,
+        part1: solved1 - parsed,
+        part2: solved2 - solved1,
+    };
+    Ok((Answers { part1, part2 }, timings))
+}
+
+/// A single day in the calendar with a type-erased solver, so all days can be stored together.
+pub struct Puzzle {
+    pub day: u32,
+    pub title: &'static str,
+    solver: fn(&str) -> Result<(Answers, Timings), ParseError>,
+}
+
+impl Puzzle {
+    pub const fn new(
+        day: u32,
+        title: &'static str,
+        solver: fn(&str) -> Result<(Answers, Timings), ParseError>,
+    ) -> Puzzle {
+        Puzzle { day, title, solver }
+    }
+
+    /// Solves both parts of the puzzle for the given input.
+    pub fn solve(&self, input: &str) -> Result<Answers, ParseError> {
+        self.solve_timed(input).map(|(answers, _)| answers)
+    }
+
+    /// Solves both parts of the puzzle for the given input and measures how long each step took.
+    pub fn solve_timed(&self, input: &str) -> Result<(Answers, Timings), ParseError> {
+        (self.solver)(input)
+    }
+
+    /// Reads the puzzle input from the given source and solves both parts of the puzzle.
+    pub fn run(&self, source: &InputSource) -> Result<Answers, Error> {
+        let input = input::load(self.day, source)?;
+        self.solve(&input).map_err(|error| Error::Parse {
+            day: self.day,
+            input: source.name(self.day),
+            error,
+        })
+    }
+}
@@ -0,0 +1,96 @@
+//! A disjoint-set forest for grouping the elements `0..n` into sets.
+
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    /// The number of elements in the set of every root.
+    sizes: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    /// Creates `len` sets that each contain a single element.
+    pub fn new(len: usize) -> UnionFind {
+        UnionFind {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            sets: len,
+        }
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Returns the representative of the set containing `element`.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        // Point everything on the way directly to the root
+        let mut current = element;
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`. Returns false if they already were the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        // Hang the smaller tree below the larger one
+        if self.sizes[a] < self.sizes[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        self.sets -= 1;
+        true
+    }
+
+    pub fn same_set(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Returns the number of elements in the set containing `element`.
+    pub fn set_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    /// Returns the number of disjoint sets.
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn union() {
+        let mut sets = UnionFind::new(5);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(3, 4));
+        assert!(sets.union(1, 4));
+        assert!(!sets.union(0, 3));
+        assert!(sets.same_set(0, 4));
+        assert!(!sets.same_set(0, 2));
+        assert_eq!(sets.set_size(3), 4);
+        assert_eq!(sets.sets(), 2);
+    }
+}
@@ -0,0 +1,564 @@
+//! The time travel device's instruction set, shared by the days that run programs on it.
+//!
+//! A program is written with an instruction per line as `<mnemonic> a b c`. The optional
+//! directive `#ip n` on the first line binds the instruction pointer to register `n`: before each
+//! instruction the instruction pointer is written to that register and afterwards it is read
+//! back, so instructions can jump by writing to the register.
+
+use crate::parse::{lines, Span};
+use crate::ParseError;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The value of a register or an immediate operand.
+pub type Value = i64;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Opcode {
+    Addr = 0,
+    Addi = 1,
+    Mulr = 2,
+    Muli = 3,
+    Banr = 4,
+    Bani = 5,
+    Borr = 6,
+    Bori = 7,
+    Setr = 8,
+    Seti = 9,
+    Gtir = 10,
+    Gtri = 11,
+    Gtrr = 12,
+    Eqir = 13,
+    Eqri = 14,
+    Eqrr = 15,
+}
+
+/// How an instruction interprets its `a` or `b` operand.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Operand {
+    Register,
+    Immediate,
+    /// The operand is not used.
+    Ignored,
+}
+
+impl Opcode {
+    /// All opcodes, indexed by their number.
+    pub const ALL: [Opcode; 16] = [
+        Opcode::Addr,
+        Opcode::Addi,
+        Opcode::Mulr,
+        Opcode::Muli,
+        Opcode::Banr,
+        Opcode::Bani,
+        Opcode::Borr,
+        Opcode::Bori,
+        Opcode::Setr,
+        Opcode::Seti,
+        Opcode::Gtir,
+        Opcode::Gtri,
+        Opcode::Gtrr,
+        Opcode::Eqir,
+        Opcode::Eqri,
+        Opcode::Eqrr,
+    ];
+
+    pub fn values() -> impl Iterator<Item = Opcode> {
+        Opcode::ALL.iter().copied()
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Addr => "addr",
+            Opcode::Addi => "addi",
+            Opcode::Mulr => "mulr",
+            Opcode::Muli => "muli",
+            Opcode::Banr => "banr",
+            Opcode::Bani => "bani",
+            Opcode::Borr => "borr",
+            Opcode::Bori => "bori",
+            Opcode::Setr => "setr",
+            Opcode::Seti => "seti",
+            Opcode::Gtir => "gtir",
+            Opcode::Gtri => "gtri",
+            Opcode::Gtrr => "gtrr",
+            Opcode::Eqir => "eqir",
+            Opcode::Eqri => "eqri",
+            Opcode::Eqrr => "eqrr",
+        }
+    }
+
+    /// Returns how the `a` and `b` operands are interpreted.
+    pub fn operands(self) -> (Operand, Operand) {
+        use Operand::*;
+        match self {
+            Opcode::Addr | Opcode::Mulr | Opcode::Banr | Opcode::Borr => (Register, Register),
+            Opcode::Addi | Opcode::Muli | Opcode::Bani | Opcode::Bori => (Register, Immediate),
+            Opcode::Setr => (Register, Ignored),
+            Opcode::Seti => (Immediate, Ignored),
+            Opcode::Gtir | Opcode::Eqir => (Immediate, Register),
+            Opcode::Gtri | Opcode::Eqri => (Register, Immediate),
+            Opcode::Gtrr | Opcode::Eqrr => (Register, Register),
+        }
+    }
+
+    /// Returns the operator used to describe the opcode, see `Instruction::describe`.
+    fn operator(self) -> Option<&'static str> {
+        match self {
+            Opcode::Addr | Opcode::Addi => Some("+"),
+            Opcode::Mulr | Opcode::Muli => Some("*"),
+            Opcode::Banr | Opcode::Bani => Some("&"),
+            Opcode::Borr | Opcode::Bori => Some("|"),
+            Opcode::Setr | Opcode::Seti => None,
+            Opcode::Gtir | Opcode::Gtri | Opcode::Gtrr => Some(">"),
+            Opcode::Eqir | Opcode::Eqri | Opcode::Eqrr => Some("=="),
+        }
+    }
+}
+
+impl Display for Opcode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self.mnemonic())
+    }
+}
+
+impl FromStr for Opcode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Opcode, String> {
+        Opcode::values()
+            .find(|opcode| opcode.mnemonic() == s)
+            .ok_or_else(|| "unknown opcode".to_owned())
+    }
+}
+
+/// An error that occurred while executing an instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fault {
+    /// An operand referred to a register that does not exist.
+    InvalidRegister(Value),
+}
+
+impl Display for Fault {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            Fault::InvalidRegister(register) => write!(f, "invalid register {}", register),
+        }
+    }
+}
+
+impl std::error::Error for Fault {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub a: Value,
+    pub b: Value,
+    pub c: Value,
+}
+
+impl Instruction {
+    pub fn new(opcode: Opcode, a: Value, b: Value, c: Value) -> Instruction {
+        Instruction { opcode, a, b, c }
+    }
+
+    /// Executes the instruction on the registers.
+    pub fn execute(&self, registers: &mut [Value]) -> Result<(), Fault> {
+        let reg = |r: Value| -> Result<Value, Fault> {
+            if r >= 0 && (r as usize) < registers.len() {
+                Ok(registers[r as usize])
+            } else {
+                Err(Fault::InvalidRegister(r))
+            }
+        };
+        let (a, b) = (self.a, self.b);
+        let result = match self.opcode {
+            Opcode::Addr => reg(a)?.wrapping_add(reg(b)?),
+            Opcode::Addi => reg(a)?.wrapping_add(b),
+            Opcode::Mulr => reg(a)?.wrapping_mul(reg(b)?),
+            Opcode::Muli => reg(a)?.wrapping_mul(b),
+            Opcode::Banr => reg(a)? & reg(b)?,
+            Opcode::Bani => reg(a)? & b,
+            Opcode::Borr => reg(a)? | reg(b)?,
+            Opcode::Bori => reg(a)? | b,
+            Opcode::Setr => reg(a)?,
+            Opcode::Seti => a,
+            Opcode::Gtir => (a > reg(b)?) as Value,
+            Opcode::Gtri => (reg(a)? > b) as Value,
+            Opcode::Gtrr => (reg(a)? > reg(b)?) as Value,
+            Opcode::Eqir => (a == reg(b)?) as Value,
+            Opcode::Eqri => (reg(a)? == b) as Value,
+            Opcode::Eqrr => (reg(a)? == reg(b)?) as Value,
+        };
+        match registers.get_mut(self.c as usize) {
+            Some(register) if self.c >= 0 => *register = result,
+            _ => return Err(Fault::InvalidRegister(self.c)),
+        }
+        Ok(())
+    }
+
+    /// Describes what the instruction does in a more readable form, e.g. `r3 = r1 + 5`. The
+    /// register bound to the instruction pointer, if any, is called `ip`.
+    pub fn describe(&self, ip_register: Option<usize>) -> String {
+        let name = |operand: Operand, value: Value| match operand {
+            Operand::Register if ip_register == Some(value as usize) => "ip".to_owned(),
+            Operand::Register => format!("r{}", value),
+            Operand::Immediate => value.to_string(),
+            Operand::Ignored => String::new(),
+        };
+        let (a_kind, b_kind) = self.opcode.operands();
+        let (a, b) = (name(a_kind, self.a), name(b_kind, self.b));
+        let c = name(Operand::Register, self.c);
+        match self.opcode.operator() {
+            Some(operator) => format!("{} = {} {} {}", c, a, operator, b),
+            None => format!("{} = {}", c, a),
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "{} {} {} {}", self.opcode, self.a, self.b, self.c)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Program {
+    /// The register the instruction pointer is bound to, if any.
+    pub ip_register: Option<usize>,
+    pub instructions: Vec<Instruction>,
+}
+
+impl Program {
+    /// Assembles a program from its textual form, see the module documentation.
+    pub fn parse(input: &str) -> Result<Program, ParseError> {
+        parse_program(input, None)
+    }
+
+    /// Like `parse` but requires the `#ip` directive and checks that it and every register
+    /// operand refer to one of `num_registers` registers, so the program can run on a `Machine`
+    /// with that many registers without faulting.
+    pub fn parse_with_registers(input: &str, num_registers: usize) -> Result<Program, ParseError> {
+        let program = parse_program(input, Some(num_registers))?;
+        if program.ip_register.is_none() {
+            return Err(ParseError::new(1, None, "expected `#ip n`"));
+        }
+        Ok(program)
+    }
+
+    /// Returns an annotated listing of the program with the address and a readable description
+    /// of every instruction.
+    pub fn disassemble(&self) -> String {
+        let mut listing = String::new();
+        if let Some(register) = self.ip_register {
+            listing.push_str(&format!("#ip {}\n", register));
+        }
+        for (address, instruction) in self.instructions.iter().enumerate() {
+            let text = instruction.to_string();
+            listing.push_str(&format!(
+                "{:3}: {:16} ; {}\n",
+                address,
+                text,
+                instruction.describe(self.ip_register)
+            ));
+        }
+        listing
+    }
+}
+
+fn parse_program(input: &str, num_registers: Option<usize>) -> Result<Program, ParseError> {
+    let mut program = Program::default();
+    let mut first_line = true;
+    for line in lines(input) {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Ok(register) = line.strip_prefix("#ip ") {
+            if !first_line {
+                return Err(line.error("`#ip` must be on the first line"));
+            }
+            let register = register.trim();
+            let value: usize = register.parse()?;
+            check_register(register, value as Value, num_registers)?;
+            program.ip_register = Some(value);
+        } else {
+            program
+                .instructions
+                .push(parse_instruction(line, num_registers)?);
+        }
+        first_line = false;
+    }
+    Ok(program)
+}
+
+fn parse_instruction(line: Span, num_registers: Option<usize>) -> Result<Instruction, ParseError> {
+    let mut parts = line.split_whitespace();
+    let opcode: Opcode = match parts.next() {
+        Some(opcode) => opcode.parse()?,
+        None => return Err(line.error("expected an instruction")),
+    };
+    let mut operand = |kind: Operand| -> Result<Value, ParseError> {
+        match parts.next() {
+            Some(operand) => {
+                let value = operand.parse()?;
+                if kind == Operand::Register {
+                    check_register(operand, value, num_registers)?;
+                }
+                Ok(value)
+            }
+            None => Err(line.error_at(line.text.len(), "expected `<opcode> a b c`")),
+        }
+    };
+    let (kind_a, kind_b) = opcode.operands();
+    let (a, b, c) = (
+        operand(kind_a)?,
+        operand(kind_b)?,
+        operand(Operand::Register)?,
+    );
+    if let Some(extra) = parts.next() {
+        return Err(extra.error("expected the end of the instruction"));
+    }
+    Ok(Instruction::new(opcode, a, b, c))
+}
+
+/// Checks that `value`, parsed from `span`, is one of `num_registers` registers if given.
+fn check_register(
+    span: Span,
+    value: Value,
+    num_registers: Option<usize>,
+) -> Result<(), ParseError> {
+    match num_registers {
+        Some(0) => Err(span.error("there are no registers")),
+        Some(n) if value < 0 || value >= n as Value => {
+            Err(span.error(format!("registers range from 0 to {}", n - 1)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Assembles the program back into its textual form.
+impl Display for Program {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        if let Some(register) = self.ip_register {
+            writeln!(f, "#ip {}", register)?;
+        }
+        for instruction in self.instructions.iter() {
+            writeln!(f, "{}", instruction)?;
+        }
+        Ok(())
+    }
+}
+
+/// Why a machine stopped running.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Exit {
+    /// The instruction pointer left the program.
+    Halted,
+    /// The maximum number of instructions was executed.
+    LimitReached,
+    /// The stop condition passed to `Machine::run_until` was met.
+    Stopped,
+}
+
+/// A program being executed.
+#[derive(Debug, Clone)]
+pub struct Machine<'a> {
+    program: &'a Program,
+    pub registers: Vec<Value>,
+    /// The address of the next instruction.
+    pub ip: Value,
+    /// The number of instructions executed so far.
+    pub steps: u64,
+}
+
+impl<'a> Machine<'a> {
+    /// Creates a machine with the given number of registers, all set to zero.
+    pub fn new(program: &'a Program, num_registers: usize) -> Machine<'a> {
+        Machine {
+            program,
+            registers: vec![0; num_registers],
+            ip: 0,
+            steps: 0,
+        }
+    }
+
+    pub fn program(&self) -> &'a Program {
+        self.program
+    }
+
+    /// Returns the next instruction or `None` if the machine has halted.
+    pub fn current_instruction(&self) -> Option<&'a Instruction> {
+        if self.ip < 0 {
+            None
+        } else {
+            self.program.instructions.get(self.ip as usize)
+        }
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.current_instruction().is_none()
+    }
+
+    /// Executes a single instruction. Returns `false` if the machine had already halted.
+    pub fn step(&mut self) -> Result<bool, Fault> {
+        let instruction = match self.current_instruction() {
+            Some(instruction) => instruction,
+            None => return Ok(false),
+        };
+
+        match self.program.ip_register {
+            Some(register) => {
+                let ip = self
+                    .registers
+                    .get_mut(register)
+                    .ok_or(Fault::InvalidRegister(register as Value))?;
+                *ip = self.ip;
+                instruction.execute(&mut self.registers)?;
+                self.ip = self.registers[register].wrapping_add(1);
+            }
+            None => {
+                instruction.execute(&mut self.registers)?;
+                self.ip += 1;
+            }
+        }
+        self.steps += 1;
+        Ok(true)
+    }
+
+    /// Runs until the program halts or, if given, `limit` more instructions have been executed.
+    pub fn run(&mut self, limit: Option<u64>) -> Result<Exit, Fault> {
+        self.run_until(limit, |_| false)
+    }
+
+    /// Like `run` but also stops before executing an instruction if `stop` returns true.
+    pub fn run_until<F>(&mut self, limit: Option<u64>, mut stop: F) -> Result<Exit, Fault>
+    where
+        F: FnMut(&Machine) -> bool,
+    {
+        let end = limit.map(|limit| self.steps + limit);
+        loop {
+            if self.is_halted() {
+                return Ok(Exit::Halted);
+            }
+            if end == Some(self.steps) {
+                return Ok(Exit::LimitReached);
+            }
+            if stop(self) {
+                return Ok(Exit::Stopped);
+            }
+            self.step()?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "#ip 0
+seti 5 0 1
+seti 6 0 2
+addi 0 1 0
+addr 1 2 3
+setr 1 0 0
+seti 8 0 4
+seti 9 0 5
+";
+
+    #[test]
+    fn run_example() {
+        let program = Program::parse(EXAMPLE).unwrap();
+        assert_eq!(program.to_string(), EXAMPLE);
+
+        let mut machine = Machine::new(&program, 6);
+        assert_eq!(machine.run(Some(2)), Ok(Exit::LimitReached));
+        assert_eq!(machine.run(None), Ok(Exit::Halted));
+        assert_eq!(machine.registers, vec![6, 5, 6, 0, 0, 9]);
+        assert_eq!(machine.steps, 5);
+    }
+
+    #[test]
+    fn disassemble() {
+        let program = Program::parse(EXAMPLE).unwrap();
+        let listing = program.disassemble();
+        assert!(listing.contains("  2: addi 0 1 0       ; ip = ip + 1\n"));
+        assert!(listing.contains("  4: setr 1 0 0       ; ip = r1\n"));
+
+        let error = Program::parse("seti 1 2\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 9: expected `<opcode> a b c`"
+        );
+        let error = Program::parse("set 1 2 3\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: invalid value `set`: unknown opcode"
+        );
+    }
+
+    #[test]
+    fn invalid_register() {
+        let mut registers = vec![0; 4];
+        let instruction = Instruction::new(Opcode::Addr, 0, 4, 1);
+        assert_eq!(
+            instruction.execute(&mut registers),
+            Err(Fault::InvalidRegister(4))
+        );
+
+        // Overflowing arithmetic wraps around
+        let mut registers = vec![Value::MAX, 2];
+        Instruction::new(Opcode::Mulr, 0, 1, 1)
+            .execute(&mut registers)
+            .unwrap();
+        assert_eq!(registers[1], -2);
+
+        // So does jumping past the largest address, which halts the program
+        let program = Program::parse("#ip 0\nseti 9223372036854775807 0 0\n").unwrap();
+        let mut machine = Machine::new(&program, 1);
+        assert_eq!(machine.run(None), Ok(Exit::Halted));
+        assert_eq!(machine.ip, Value::MIN);
+    }
+
+    #[test]
+    fn ip_after_blank_lines() {
+        let program = Program::parse("\n#ip 1\nseti 5 0 1\n").unwrap();
+        assert_eq!(program.ip_register, Some(1));
+        assert!(Program::parse("seti 5 0 1\n#ip 1\n").is_err());
+    }
+
+    #[test]
+    fn parse_with_registers() {
+        assert_eq!(
+            Program::parse_with_registers(EXAMPLE, 6).unwrap(),
+            Program::parse(EXAMPLE).unwrap()
+        );
+
+        let error = Program::parse_with_registers("seti 5 0 1\n", 6).unwrap_err();
+        assert_eq!(error.to_string(), "line 1: expected `#ip n`");
+        let error = Program::parse_with_registers("#ip 6\nseti 5 0 1\n", 6).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 5: registers range from 0 to 5"
+        );
+        let error = Program::parse_with_registers("#ip 0\nseti 5 0 1\n", 0).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 5: there are no registers"
+        );
+        // The immediate operand of seti may be anything, but c is always a register
+        assert!(Program::parse_with_registers("#ip 0\nseti 9 0 1\n", 6).is_ok());
+        let error = Program::parse_with_registers("#ip 0\nseti 9 0 6\n", 6).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 10: registers range from 0 to 5"
+        );
+        let error = Program::parse_with_registers("#ip 0\ngtir 9 7 1\n", 6).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 8: registers range from 0 to 5"
+        );
+    }
+}